@@ -0,0 +1,65 @@
+use std::env;
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use aoc::input::Source;
+use aoc_2022::DAYS;
+use aoc_common::Part;
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+
+/// Benchmarks every day on the checked-in example input, and on the real input from
+/// `AOC_INPUT_DIR` (or `in`) when one is available.
+fn solutions(c: &mut Criterion) {
+    // Solved with the real input's parameters, which are wrong and slow for these examples
+    const NO_EXAMPLE: &[u8] = &[15];
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let dir = env::var_os("AOC_INPUT_DIR").map_or_else(|| root.join("in"), PathBuf::from);
+    let sources = [
+        (Source::Set("example".to_string()), root.join("in")),
+        (Source::Set(Source::DEFAULT.to_string()), dir),
+    ];
+
+    for day in DAYS.iter().copied() {
+        for (source, dir) in &sources {
+            if source.name() == Some("example") && NO_EXAMPLE.contains(&day.day()) {
+                eprintln!(
+                    "skipping day {} ({source}): needs other parameters",
+                    day.day()
+                );
+                continue;
+            }
+
+            let Ok(input) = source.read(dir, day.day()) else {
+                eprintln!("skipping day {} ({source}): no input", day.day());
+                continue;
+            };
+
+            let mut group = c.benchmark_group(format!("day{}/{source}", day.day()));
+
+            if source.name() == Some(Source::DEFAULT) {
+                group.sample_size(10);
+            }
+
+            group.bench_function("parse", |b| b.iter(|| day.parse(black_box(&input))));
+
+            let Ok(parsed) = day.parse(&input) else {
+                eprintln!("skipping day {} ({source}): invalid input", day.day());
+                continue;
+            };
+
+            for part in Part::ALL {
+                group.bench_function(format!("part{part}"), |b| {
+                    b.iter(|| parsed.solve(black_box(part)));
+                });
+            }
+
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
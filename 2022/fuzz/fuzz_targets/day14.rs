@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2022::day14::Cave;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    aoc_2022_fuzz::parse(14, data);
+    aoc_2022_fuzz::roundtrip::<Cave>(data);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2022::day7::FileSystem;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    aoc_2022_fuzz::parse(7, data);
+    aoc_2022_fuzz::roundtrip::<FileSystem>(data);
+});
@@ -0,0 +1,41 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::str;
+use std::str::FromStr;
+
+use aoc_2022::YEAR;
+
+/// Parses `data` as the given day's puzzle input, which may fail but must not panic.
+///
+/// Each day's parser is built from the `FromStr` implementations of its puzzle types, so this
+/// exercises all of them.
+pub fn parse(day: u8, data: &[u8]) {
+    let Ok(input) = str::from_utf8(data) else {
+        return;
+    };
+
+    let day = YEAR.get(day).unwrap_or_else(|| panic!("no solution for day {day}"));
+    let _ = day.parse(input);
+}
+
+/// Checks that whatever parses as `T` parses back into an equal value after being rendered with
+/// `Display`.
+pub fn roundtrip<T>(data: &[u8])
+where
+    T: FromStr + Display + PartialEq + Debug,
+    T::Err: Display,
+{
+    let Some(parsed) = str::from_utf8(data)
+        .ok()
+        .and_then(|input| input.parse::<T>().ok())
+    else {
+        return;
+    };
+
+    let rendered = parsed.to_string();
+    let reparsed = rendered
+        .parse::<T>()
+        .unwrap_or_else(|err| panic!("rendered input does not parse: {err}\n{rendered}"));
+
+    assert_eq!(reparsed, parsed, "rendered as:\n{rendered}");
+}
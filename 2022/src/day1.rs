@@ -1,13 +1,15 @@
 use std::collections::BinaryHeap;
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
+use aoc_common::Solution;
+use aoc_common::parse;
+
+pub struct Day1;
 
 #[derive(Debug)]
-struct Elf {
+pub struct Elf {
     calories: Vec<u32>,
 }
 
@@ -18,7 +20,7 @@ impl FromStr for Elf {
         Ok(Self {
             calories: calories
                 .lines()
-                .map(str::parse)
+                .map(parse::from_str)
                 .collect::<Result<Vec<_>, _>>()?,
         })
     }
@@ -43,29 +45,18 @@ fn part2(elves: &[Elf]) -> u64 {
         .sum()
 }
 
-fn main() -> Result<()> {
-    let elves = fs::read_to_string("in/day1.txt")?
-        .split("\n\n")
-        .map(Elf::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&elves);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 72_070);
-    };
+impl Solution for Day1 {
+    type Input = Vec<Elf>;
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&elves);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.split("\n\n").map(Elf::from_str).collect()
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 211_805);
-    };
+    fn part1(elves: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(elves))
+    }
 
-    Ok(())
+    fn part2(elves: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(elves))
+    }
 }
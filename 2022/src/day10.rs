@@ -0,0 +1,697 @@
+use std::collections::HashMap;
+use std::slice;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use aoc_common::Grid;
+use aoc_common::Solution;
+use aoc_common::ocr;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Instruction {
+    Noop,
+    /// Adds to a register, e.g. `addx 3` to `x`.
+    Add(Register, i32),
+}
+
+/// An instruction without its operands, which decides how many cycles it takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Noop,
+    Add,
+}
+
+/// A program, written one instruction per line as in the puzzle input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+/// What the CRT shows: the letters drawn on it, or its pixels when they are not letters, such as
+/// the test pattern drawn by the example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crt {
+    pixels: String,
+    letters: Option<String>,
+}
+
+/// One of the 26 registers, named `a` to `z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Register(u8);
+
+/// The values of the registers, all 0 apart from `x`, which starts at 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Registers {
+    values: [i64; 26],
+    /// The registers written so far, as a bit per register, which are the ones displayed.
+    used: u32,
+}
+
+/// Where a [`Trace`] stops when resumed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// During the given cycle, counting from 1.
+    Cycle(usize),
+    /// During the first cycle in which the register holds the value, after holding another.
+    Register(Register, i64),
+}
+
+/// What happens during a cycle: which step of which instruction, and the registers, which only
+/// change once an instruction completes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    number: usize,
+    instruction: Instruction,
+    step: usize,
+    cost: usize,
+    registers: Registers,
+}
+
+/// Runs programs, taking as many cycles for each instruction as its opcode costs.
+#[derive(Clone, Debug)]
+pub struct Cpu {
+    costs: HashMap<Opcode, usize>,
+}
+
+/// A program running cycle by cycle, yielding each [`Cycle`].
+#[derive(Clone, Debug)]
+pub struct Trace<'a> {
+    cpu: Cpu,
+    program: slice::Iter<'a, Instruction>,
+    registers: Registers,
+    /// The registers during the last cycle yielded, for register breakpoints.
+    previous: Option<Registers>,
+    cycle: usize,
+    /// The instruction being executed, with the number of cycles spent on it and its cost.
+    current: Option<(Instruction, usize, usize)>,
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(instruction: &str) -> Result<Self> {
+        let (mnemonic, operand) = instruction.split_once(' ').unwrap_or((instruction, ""));
+
+        if mnemonic == "noop" {
+            if !operand.is_empty() {
+                return Err(ParseError::new(operand, "unexpected operand").into());
+            }
+
+            Ok(Self::Noop)
+        } else if let Some(register) = mnemonic.strip_prefix("add") {
+            let register = register.parse()?;
+            let val = parse::from_str(operand)?;
+            Ok(Self::Add(register, val))
+        } else {
+            Err(ParseError::new(mnemonic, "unknown instruction").into())
+        }
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Noop => write!(f, "noop"),
+            Self::Add(register, val) => write!(f, "add{register} {val}"),
+        }
+    }
+}
+
+impl Instruction {
+    #[must_use]
+    pub const fn opcode(self) -> Opcode {
+        match self {
+            Self::Noop => Opcode::Noop,
+            Self::Add(..) => Opcode::Add,
+        }
+    }
+
+    /// Applies the instruction's effect, once all of its cycles have passed.
+    fn apply(self, registers: &mut Registers) {
+        match self {
+            Self::Noop => {}
+            Self::Add(register, val) => {
+                registers.set(register, registers.get(register) + i64::from(val));
+            }
+        }
+    }
+}
+
+impl FromStr for Program {
+    type Err = Error;
+
+    fn from_str(program: &str) -> Result<Self> {
+        Ok(Self {
+            instructions: program
+                .lines()
+                .map(Instruction::from_str)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for instruction in &self.instructions {
+            writeln!(f, "{instruction}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Vec<Instruction>> for Program {
+    fn from(instructions: Vec<Instruction>) -> Self {
+        Self { instructions }
+    }
+}
+
+impl Program {
+    #[must_use]
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Synthesizes a program of `noop` and `addx` whose CRT draws `bitmap`, 6 rows of 40 pixels
+    /// drawn with `#` and `.`, when run for the 240 cycles of a screen.
+    ///
+    /// Goes back from the last cycle to find every value of `x` each cycle can be drawn with and
+    /// still finish the bitmap, then forward from `x` = 1, preferring `noop` whenever it will do.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bitmap is not 6 rows of 40 `#` and `.`, or no program draws it:
+    /// `x` only changes after an `addx` completes, so it holds for at least two cycles, and it
+    /// starts at 1, so the top left pixel is always lit.
+    pub fn compile(bitmap: &str) -> Result<Self> {
+        const WIDTH: usize = 40;
+        const HEIGHT: usize = 6;
+        // Any further left or right and `x` lights nothing, like -2 and 41
+        const MIN: i64 = -2;
+        const MAX: i64 = 41;
+
+        let pixels = Grid::parse(bitmap, |_, pixel| match pixel {
+            "#" => Ok(true),
+            "." => Ok(false),
+            _ => Err(ParseError::new(pixel, "expected '#' or '.'").into()),
+        })?;
+
+        if (pixels.width(), pixels.height()) != (WIDTH, HEIGHT) {
+            bail!(
+                "expected {HEIGHT} rows of {WIDTH} pixels, not {} of {}",
+                pixels.height(),
+                pixels.width()
+            );
+        }
+
+        let lit = pixels.rows().flatten().copied().collect::<Vec<_>>();
+        #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+        let draws = |cycle: usize, x: i64| lit[cycle] == ((x - (cycle % WIDTH) as i64).abs() <= 1);
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        let index = |x: i64| (x - MIN) as usize;
+
+        // The instruction to run at each cycle with each `x` to draw the pixels from there on, if
+        // any does, with a row past the end where every `x` is done
+        let mut plan = vec![vec![None; index(MAX) + 1]; lit.len() + 1];
+        plan[lit.len()].fill(Some(Instruction::Noop));
+
+        for cycle in (0..lit.len()).rev() {
+            for x in (MIN..=MAX).filter(|&x| draws(cycle, x)) {
+                plan[cycle][index(x)] = if plan[cycle + 1][index(x)].is_some() {
+                    Some(Instruction::Noop)
+                } else if cycle + 1 < lit.len() && draws(cycle + 1, x) {
+                    // Keeping `x` where it is when that will do, as an `addx 0`
+                    std::iter::once(x)
+                        .chain(MIN..=MAX)
+                        .find(|&next| plan[cycle + 2][index(next)].is_some())
+                        .map(|next| {
+                            #[allow(clippy::cast_possible_truncation)]
+                            Instruction::Add(Register::X, (next - x) as i32)
+                        })
+                } else {
+                    None
+                };
+            }
+        }
+
+        let mut instructions = Vec::new();
+        let mut registers = Registers::default();
+        let mut cycle = 0;
+
+        while cycle < lit.len() {
+            let Some(instruction) = plan[cycle][index(registers.get(Register::X))] else {
+                bail!("no program draws the bitmap");
+            };
+
+            instructions.push(instruction);
+            instruction.apply(&mut registers);
+            cycle += instruction.opcode().cycles();
+        }
+
+        Ok(Self { instructions })
+    }
+}
+
+impl Opcode {
+    pub const ALL: [Self; 2] = [Self::Noop, Self::Add];
+
+    /// The number of cycles the opcode takes, unless the [`Cpu`] says otherwise.
+    #[must_use]
+    pub const fn cycles(self) -> usize {
+        match self {
+            Self::Noop => 1,
+            Self::Add => 2,
+        }
+    }
+}
+
+impl FromStr for Register {
+    type Err = Error;
+
+    fn from_str(register: &str) -> Result<Self> {
+        match register.as_bytes() {
+            &[name @ b'a'..=b'z'] => Ok(Self(name - b'a')),
+            _ => Err(ParseError::new(register, "unknown register").into()),
+        }
+    }
+}
+
+impl std::fmt::Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(b'a' + self.0))
+    }
+}
+
+impl Register {
+    pub const X: Self = Self(b'x' - b'a');
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        let mut registers = Self {
+            values: [0; 26],
+            used: 0,
+        };
+        registers.set(Register::X, 1);
+
+        registers
+    }
+}
+
+impl std::fmt::Display for Registers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let used = (0..26).filter(|i| self.used & (1 << i) != 0).map(Register);
+
+        for (i, register) in used.enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{register}={}", self.get(register))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Registers {
+    #[must_use]
+    pub const fn get(&self, register: Register) -> i64 {
+        self.values[register.0 as usize]
+    }
+
+    pub const fn set(&mut self, register: Register, val: i64) {
+        self.values[register.0 as usize] = val;
+        self.used |= 1 << register.0;
+    }
+}
+
+impl Breakpoint {
+    /// Whether the trace stops at `cycle`, which followed a cycle with the `previous` registers.
+    fn hit(self, cycle: &Cycle, previous: Option<&Registers>) -> bool {
+        match self {
+            Self::Cycle(at) => cycle.number == at,
+            Self::Register(register, val) => {
+                cycle.registers.get(register) == val
+                    && previous.is_none_or(|previous| previous.get(register) != val)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>4} {:<10} {}/{} {}",
+            self.number,
+            self.instruction.to_string(),
+            self.step,
+            self.cost,
+            self.registers
+        )
+    }
+}
+
+impl Cycle {
+    /// The number of the cycle, counting from 1.
+    #[must_use]
+    pub const fn number(&self) -> usize {
+        self.number
+    }
+
+    #[must_use]
+    pub const fn instruction(&self) -> Instruction {
+        self.instruction
+    }
+
+    #[must_use]
+    pub const fn registers(&self) -> &Registers {
+        &self.registers
+    }
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self {
+            costs: Opcode::ALL
+                .into_iter()
+                .map(|opcode| (opcode, opcode.cycles()))
+                .collect(),
+        }
+    }
+}
+
+impl Cpu {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes `opcode` take `cycles` cycles instead; an instruction that takes none completes
+    /// without a cycle of its own.
+    #[must_use]
+    pub fn cost(mut self, opcode: Opcode, cycles: usize) -> Self {
+        self.costs.insert(opcode, cycles);
+        self
+    }
+
+    /// Starts running `program`, with the registers in their initial state.
+    #[must_use]
+    pub fn trace(self, program: &[Instruction]) -> Trace<'_> {
+        Trace {
+            cpu: self,
+            program: program.iter(),
+            registers: Registers::default(),
+            previous: None,
+            cycle: 0,
+            current: None,
+        }
+    }
+}
+
+impl Iterator for Trace<'_> {
+    type Item = Cycle;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.current {
+                Some((instruction, step, cost)) if step < cost => {
+                    self.cycle += 1;
+                    self.current = Some((instruction, step + 1, cost));
+                    self.previous = Some(self.registers);
+
+                    return Some(Cycle {
+                        number: self.cycle,
+                        instruction,
+                        step: step + 1,
+                        cost,
+                        registers: self.registers,
+                    });
+                }
+                Some((instruction, ..)) => instruction.apply(&mut self.registers),
+                None => {}
+            }
+
+            let &instruction = self.program.next()?;
+            let cost = self.cpu.costs[&instruction.opcode()];
+
+            self.current = Some((instruction, 0, cost));
+        }
+    }
+}
+
+impl Trace<'_> {
+    /// Runs until the next cycle at which any of the `breakpoints` is hit, or returns `None` if
+    /// the program stops first.
+    pub fn resume(&mut self, breakpoints: &[Breakpoint]) -> Option<Cycle> {
+        loop {
+            let previous = self.previous;
+            let cycle = self.next()?;
+
+            if breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.hit(&cycle, previous.as_ref()))
+            {
+                return Some(cycle);
+            }
+        }
+    }
+
+    /// The number of cycles run so far.
+    #[must_use]
+    pub const fn cycles(&self) -> usize {
+        self.cycle
+    }
+
+    /// The registers as they are now, including the effect of the last instruction once the
+    /// program has stopped.
+    #[must_use]
+    pub const fn registers(&self) -> &Registers {
+        &self.registers
+    }
+}
+
+fn part1(program: &[Instruction]) -> Result<i64> {
+    const CYCLES: [usize; 6] = [20, 60, 100, 140, 180, 220];
+
+    let mut trace = Cpu::new().trace(program);
+    let breakpoints = CYCLES.map(Breakpoint::Cycle);
+
+    CYCLES
+        .iter()
+        .map(|_| {
+            let cycle = trace
+                .resume(&breakpoints)
+                .ok_or_else(|| anyhow!("the program stops after {} cycles", trace.cycles()))?;
+
+            Ok(i64::try_from(cycle.number())? * cycle.registers().get(Register::X))
+        })
+        .sum()
+}
+
+/// Draws the CRT's pixels as the program runs, with `#` and `.`.
+fn draw(program: &[Instruction]) -> String {
+    let mut pixels = String::new();
+
+    for cycle in Cpu::new().trace(program) {
+        #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+        let col = ((cycle.number() - 1) % 40) as i64;
+
+        if col == 0 && cycle.number() > 1 {
+            pixels.push('\n');
+        }
+
+        let x = cycle.registers().get(Register::X);
+        pixels.push(if (x - col).abs() <= 1 { '#' } else { '.' });
+    }
+
+    pixels
+}
+
+impl Crt {
+    /// Reads the letters drawn with `pixels`, keeping the pixels as they are if they cannot be
+    /// read, e.g. as they are not letters or the screen is not as tall as a font.
+    fn new(pixels: String) -> Self {
+        let letters = ocr::read(&pixels).ok();
+
+        Self { pixels, letters }
+    }
+}
+
+impl std::fmt::Display for Crt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.letters.as_ref().unwrap_or(&self.pixels))
+    }
+}
+
+/// Reads the letters the program draws on the CRT.
+fn part2(program: &[Instruction]) -> Crt {
+    Crt::new(self::draw(program))
+}
+
+impl Solution for Day10 {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(program: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part1(program.instructions())
+    }
+
+    fn part2(program: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(program.instructions()))
+    }
+
+    fn trace(program: &Self::Input) -> Option<Box<dyn Iterator<Item = String> + '_>> {
+        Some(Box::new(
+            Cpu::new()
+                .trace(program.instructions())
+                .map(|cycle| cycle.to_string()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::ocr::UnknownGlyph;
+
+    use super::*;
+
+    const PROGRAM: &str = "noop\naddx 3\naddx -5\n";
+
+    #[test]
+    fn example() {
+        let program = Day10::parse(include_str!("../in/example/day10.txt")).unwrap();
+
+        assert_eq!(Day10::part1(&program).unwrap().to_string(), "13140");
+        assert_eq!(
+            self::draw(program.instructions()),
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        );
+
+        // The example draws a test pattern rather than letters, which is shown as it is
+        let crt = self::part2(program.instructions());
+        let err = ocr::read(&crt.pixels)
+            .unwrap_err()
+            .downcast::<UnknownGlyph>()
+            .unwrap();
+
+        assert_eq!(err.columns(), 0..40);
+        assert_eq!(crt.letters, None);
+        assert_eq!(crt.to_string(), crt.pixels);
+
+        // As is a screen too short for any font
+        let crt = self::part2(Day10::parse(PROGRAM).unwrap().instructions());
+
+        assert_eq!(crt.letters, None);
+        assert_eq!(crt.to_string(), "#####");
+    }
+
+    #[test]
+    fn trace() {
+        let program = Day10::parse(PROGRAM).unwrap();
+        let mut trace = Cpu::new().trace(program.instructions());
+
+        assert_eq!(
+            trace
+                .by_ref()
+                .map(|cycle| cycle.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "   1 noop       1/1 x=1",
+                "   2 addx 3     1/2 x=1",
+                "   3 addx 3     2/2 x=1",
+                "   4 addx -5    1/2 x=4",
+                "   5 addx -5    2/2 x=4",
+            ]
+        );
+        assert_eq!(trace.cycles(), 5);
+        assert_eq!(trace.registers().get(Register::X), -1);
+    }
+
+    #[test]
+    fn registers_and_costs() {
+        let program = Day10::parse("addy 2\naddx 1\nnoop\naddy -2\n").unwrap();
+        let cycles = Cpu::new()
+            .cost(Opcode::Add, 3)
+            .cost(Opcode::Noop, 0)
+            .trace(program.instructions())
+            .collect::<Vec<_>>();
+
+        // `noop` takes no cycles at all, and `y` is shown once written
+        assert_eq!(cycles.len(), 9);
+        assert_eq!(cycles[3].to_string(), "   4 addx 1     1/3 x=1 y=2");
+        assert_eq!(cycles[6].to_string(), "   7 addy -2    1/3 x=2 y=2");
+        assert!(Day10::parse("addX 1\n").is_err());
+        assert!(Day10::parse("noop 1\n").is_err());
+    }
+
+    #[test]
+    fn breakpoints() {
+        let program = Day10::parse(PROGRAM).unwrap();
+        let mut trace = Cpu::new().trace(program.instructions());
+        let breakpoints = [Breakpoint::Cycle(2), Breakpoint::Register(Register::X, 4)];
+
+        let cycles = std::iter::from_fn(|| trace.resume(&breakpoints))
+            .map(|cycle| (cycle.number(), cycle.instruction()))
+            .collect::<Vec<_>>();
+
+        // Stops when `x` becomes 4, but not again while it stays 4
+        assert_eq!(
+            cycles,
+            [
+                (2, Instruction::Add(Register::X, 3)),
+                (4, Instruction::Add(Register::X, -5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn compile() {
+        // Letters are 5 columns apart, and the last is followed by a blank column
+        let bitmap = ocr::draw("EHZFZHCZ", 6)
+            .unwrap()
+            .lines()
+            .map(|row| format!("{row:.<40}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let program = Program::compile(&bitmap).unwrap();
+
+        assert_eq!(self::draw(program.instructions()), bitmap);
+        assert_eq!(self::part2(program.instructions()).to_string(), "EHZFZHCZ");
+        assert_eq!(program.to_string().parse::<Program>().unwrap(), program);
+        assert!(program.instructions().iter().all(|instruction| matches!(
+            instruction,
+            Instruction::Noop | Instruction::Add(Register::X, _)
+        )));
+
+        // `x` starts at 1, lighting the top left pixel, and holds for 2 cycles after that
+        let dark = bitmap.replacen('#', ".", 1);
+        let flicker = format!("#.#{}", &bitmap[3..]);
+
+        for bitmap in [
+            dark,
+            flicker,
+            bitmap[1..].to_string(),
+            bitmap.replace('#', "x"),
+        ] {
+            assert!(Program::compile(&bitmap).is_err());
+        }
+    }
+}
@@ -0,0 +1,785 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Ok;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use aoc_common::Solution;
+use aoc_common::cycle;
+use aoc_common::cycle::Cycle;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use num_bigint::BigUint;
+
+pub struct Day11;
+
+#[derive(Clone, Copy, Debug)]
+enum Operator {
+    Add,
+    Mul,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Operand {
+    Old,
+    Val(u64),
+}
+
+#[derive(Clone, Debug)]
+struct Operation {
+    lhs: Operand,
+    rhs: Operand,
+    op: Operator,
+}
+
+#[derive(Clone, Debug)]
+struct Test {
+    divisible_by: u64,
+    pass: usize,
+    fail: usize,
+}
+
+/// An item, numbered in the order the monkeys start with them.
+#[derive(Clone, Debug)]
+struct Item<W> {
+    id: usize,
+    worry: W,
+}
+
+#[derive(Clone, Debug)]
+struct Monkey<W = u64> {
+    items: VecDeque<Item<W>>,
+    operation: Operation,
+    test: Test,
+}
+
+/// The monkeys and the items they hold, with worry levels kept as `W`: a `u64` that fails on
+/// overflow, as parsed, or a [`BigUint`] that never does.
+#[derive(Clone, Debug)]
+pub struct Game<W = u64> {
+    monkeys: Vec<Monkey<W>>,
+    inspections: Vec<usize>,
+    divisible_by_lcm: u64,
+    /// The number of rounds played so far.
+    round: usize,
+    /// Where each item has been, by ID, once tracking has been turned on.
+    lineages: Option<Vec<Lineage>>,
+}
+
+/// Every inspection of an item since tracking was turned on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lineage {
+    id: usize,
+    hops: Vec<Hop>,
+}
+
+/// An inspection of an item by a monkey, which then throws it on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hop {
+    /// The round of the inspection, counting from 1.
+    pub round: usize,
+    pub monkey: usize,
+}
+
+/// When an item's path through the monkeys repeats, with worry reduced modulo the product of the
+/// divisors.
+///
+/// Once the item starts a round with the same monkey and worry level as before, it can only do
+/// the same again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Period {
+    /// The number of rounds played when the item first starts a round it repeats later.
+    pub start: usize,
+    /// The number of rounds between repeats.
+    pub length: usize,
+}
+
+/// How worry levels are kept down after each inspection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relief {
+    /// Divided by 3, as in part 1.
+    Divide,
+    /// Reduced modulo the product of the divisors, which every monkey's test agrees with, as in
+    /// part 2.
+    Modulo,
+    /// Not at all, as part 2 would be played without the shortcut; only [`BigUint`] worry levels
+    /// last more than a few rounds.
+    Exact,
+}
+
+/// A worry level, with the arithmetic that monkeys do on it.
+pub trait Worry: Clone + From<u64> {
+    /// # Errors
+    ///
+    /// Returns an error if the sum does not fit.
+    fn add(&self, rhs: &Self) -> Result<Self>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the product does not fit.
+    fn mul(&self, rhs: &Self) -> Result<Self>;
+
+    #[must_use]
+    fn div(&self, divisor: u64) -> Self;
+
+    #[must_use]
+    fn rem(&self, divisor: u64) -> Self;
+
+    #[must_use]
+    fn is_multiple_of(&self, divisor: u64) -> bool;
+}
+
+impl TryFrom<char> for Operator {
+    type Error = Error;
+
+    fn try_from(operator: char) -> Result<Self> {
+        match operator {
+            '+' => Ok(Self::Add),
+            '*' => Ok(Self::Mul),
+            _ => bail!("invalid operator '{operator}'"),
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = Error;
+
+    fn from_str(operand: &str) -> Result<Self> {
+        match operand {
+            "old" => Ok(Self::Old),
+            _ => Ok(Self::Val(parse::from_str(operand)?)),
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = Error;
+
+    fn from_str(operation: &str) -> Result<Self> {
+        let operation = parse::strip_prefix(operation.trim_ascii_start(), "Operation: new = ")?;
+        let end = &operation[operation.len()..];
+        let mut operation = operation.split_ascii_whitespace();
+
+        let lhs = operation
+            .next()
+            .ok_or_else(|| ParseError::new(end, "missing lhs in operation"))?
+            .parse()?;
+        let op = operation
+            .next()
+            .map(parse::char::<Operator>)
+            .ok_or_else(|| ParseError::new(end, "missing op in operation"))??;
+        let rhs = operation
+            .next()
+            .ok_or_else(|| ParseError::new(end, "missing rhs in operation"))?
+            .parse()?;
+
+        Ok(Self { lhs, rhs, op })
+    }
+}
+
+impl FromStr for Test {
+    type Err = Error;
+
+    fn from_str(test: &str) -> Result<Self> {
+        let end = &test[test.len()..];
+        let mut lines = test.lines().map(str::trim_ascii_start);
+        let mut next = |prefix: &str| {
+            lines
+                .next()
+                .ok_or_else(|| ParseError::new(end, format!("missing '{}'", prefix.trim_end())))
+                .and_then(|line| parse::strip_prefix(line, prefix))
+        };
+
+        let divisible_by = next("Test: divisible by ")?;
+
+        Ok(Self {
+            divisible_by: match parse::from_str(divisible_by)? {
+                0 => return Err(ParseError::new(divisible_by, "cannot divide by zero").into()),
+                divisible_by => divisible_by,
+            },
+            pass: parse::from_str(next("If true: throw to monkey ")?)?,
+            fail: parse::from_str(next("If false: throw to monkey ")?)?,
+        })
+    }
+}
+
+impl FromStr for Monkey {
+    type Err = Error;
+
+    fn from_str(monkey: &str) -> Result<Self> {
+        let end = &monkey[monkey.len()..];
+        let missing = |what| ParseError::new(end, format!("missing {what} in monkey info"));
+        let mut lines = monkey.splitn(4, '\n');
+
+        let id = lines.next().ok_or_else(|| missing("id"))?;
+        let id = parse::strip_prefix(id, "Monkey ")?;
+        let _id = id
+            .strip_suffix(':')
+            .ok_or_else(|| ParseError::new(&id[id.len()..], "expected ':'"))
+            .and_then(parse::from_str::<usize>)?;
+        let items = lines
+            .next()
+            .ok_or_else(|| missing("starting items"))
+            .and_then(|items| parse::strip_prefix(items.trim_ascii_start(), "Starting items: "))?
+            .split(", ")
+            .map(|worry| {
+                // Numbered by the game, which knows about the other monkeys' items
+                Ok(Item {
+                    id: 0,
+                    worry: parse::from_str(worry)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        let operation = lines
+            .next()
+            .ok_or_else(|| missing("operation"))
+            .map(Operation::from_str)??;
+        let test = lines
+            .next()
+            .ok_or_else(|| missing("test"))
+            .map(Test::from_str)??;
+
+        Ok(Self {
+            items,
+            operation,
+            test,
+        })
+    }
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut monkeys = input
+            .split("\n\n")
+            .map(Monkey::from_str)
+            .collect::<Result<Vec<_>>>()?;
+        let inspections = vec![0; monkeys.len()];
+
+        for (i, monkey) in monkeys.iter().enumerate() {
+            if let Some(target) = [monkey.test.pass, monkey.test.fail]
+                .into_iter()
+                .find(|&target| target >= monkeys.len())
+            {
+                bail!("monkey {i} throws to monkey {target}, which does not exist");
+            }
+
+            // It would keep inspecting and throwing the item to itself within a round
+            if [monkey.test.pass, monkey.test.fail].contains(&i) {
+                bail!("monkey {i} throws to itself");
+            }
+        }
+
+        // Note: all `divisible_by` values are prime, so no need to compute LCM
+        let divisible_by_lcm = monkeys
+            .iter()
+            .try_fold(1_u64, |lcm, monkey| {
+                lcm.checked_mul(monkey.test.divisible_by)
+            })
+            .ok_or_else(|| anyhow!("the product of the divisors is too large"))?;
+
+        for (id, item) in monkeys
+            .iter_mut()
+            .flat_map(|monkey| &mut monkey.items)
+            .enumerate()
+        {
+            item.id = id;
+        }
+
+        Ok(Self {
+            monkeys,
+            inspections,
+            divisible_by_lcm,
+            round: 0,
+            lineages: None,
+        })
+    }
+}
+
+impl Worry for u64 {
+    fn add(&self, rhs: &Self) -> Result<Self> {
+        self.checked_add(*rhs)
+            .ok_or_else(|| anyhow!("worry level {self} + {rhs} overflows"))
+    }
+
+    fn mul(&self, rhs: &Self) -> Result<Self> {
+        self.checked_mul(*rhs)
+            .ok_or_else(|| anyhow!("worry level {self} * {rhs} overflows"))
+    }
+
+    fn div(&self, divisor: u64) -> Self {
+        self / divisor
+    }
+
+    fn rem(&self, divisor: u64) -> Self {
+        self % divisor
+    }
+
+    fn is_multiple_of(&self, divisor: u64) -> bool {
+        Self::is_multiple_of(*self, divisor)
+    }
+}
+
+impl Worry for BigUint {
+    fn add(&self, rhs: &Self) -> Result<Self> {
+        Ok(self + rhs)
+    }
+
+    fn mul(&self, rhs: &Self) -> Result<Self> {
+        Ok(self * rhs)
+    }
+
+    fn div(&self, divisor: u64) -> Self {
+        self / divisor
+    }
+
+    fn rem(&self, divisor: u64) -> Self {
+        self % divisor
+    }
+
+    fn is_multiple_of(&self, divisor: u64) -> bool {
+        self % divisor == Self::ZERO
+    }
+}
+
+impl Operator {
+    fn apply<W: Worry>(self, lhs: &W, rhs: &W) -> Result<W> {
+        match self {
+            Self::Add => lhs.add(rhs),
+            Self::Mul => lhs.mul(rhs),
+        }
+    }
+}
+
+impl Operation {
+    fn apply<W: Worry>(&self, old: &W) -> Result<W> {
+        let operand = |operand| match operand {
+            Operand::Old => old.clone(),
+            Operand::Val(val) => W::from(val),
+        };
+
+        self.op.apply(&operand(self.lhs), &operand(self.rhs))
+    }
+}
+
+impl<W: Worry> Monkey<W> {
+    fn inspect(&self, item: &W, relief: Relief, lcm: u64) -> Result<W> {
+        let worry = self.operation.apply(item)?;
+
+        Ok(match relief {
+            Relief::Divide => worry.div(3),
+            Relief::Modulo => worry.rem(lcm),
+            Relief::Exact => worry,
+        })
+    }
+
+    fn test(&self, item: &W) -> usize {
+        if item.is_multiple_of(self.test.divisible_by) {
+            self.test.pass
+        } else {
+            self.test.fail
+        }
+    }
+}
+
+impl Game {
+    /// The same game with worry levels that never overflow, however large they get.
+    #[must_use]
+    pub fn exact(&self) -> Game<BigUint> {
+        Game {
+            monkeys: self
+                .monkeys
+                .iter()
+                .map(|monkey| Monkey {
+                    items: monkey
+                        .items
+                        .iter()
+                        .map(|item| Item {
+                            id: item.id,
+                            worry: BigUint::from(item.worry),
+                        })
+                        .collect(),
+                    operation: monkey.operation.clone(),
+                    test: monkey.test.clone(),
+                })
+                .collect(),
+            inspections: self.inspections.clone(),
+            divisible_by_lcm: self.divisible_by_lcm,
+            round: self.round,
+            lineages: self.lineages.clone(),
+        }
+    }
+
+    /// Finds when the path of the item with the given ID starts repeating, were the game played
+    /// on from here with [`Relief::Modulo`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such item, a worry level overflows, or the path does not
+    /// repeat within as many rounds as are followed.
+    pub fn period(&self, id: usize) -> Result<Period> {
+        let (monkey, item) = self
+            .items()
+            .find(|(_, item)| item.id == id)
+            .ok_or_else(|| anyhow!("there is no item {id}"))?;
+        let path = self.path(monkey, item.worry)?;
+
+        Ok(Period {
+            start: self.round + path.start(),
+            length: path.period(),
+        })
+    }
+
+    /// The monkey business after `rounds` more rounds with [`Relief::Modulo`], as
+    /// [`play`](Self::play) would give, but following each item only until its path repeats.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a worry level, an inspection count or the monkey business overflows, or
+    /// the path of an item does not repeat within as many rounds as are followed.
+    pub fn extrapolate(&self, rounds: usize) -> Result<usize> {
+        let mut game = self.clone();
+
+        for (monkey, item) in self.items() {
+            let path = self.path(monkey, item.worry)?;
+            let (prefix, cycle) = path.metrics()[1..].split_at(path.start());
+            let mut count = |rounds: &[Vec<usize>], times: usize| {
+                for &monkey in rounds.iter().flatten() {
+                    game.inspections[monkey] = game.inspections[monkey]
+                        .checked_add(times)
+                        .ok_or_else(|| anyhow!("the inspections of monkey {monkey} overflow"))?;
+                }
+
+                Ok(())
+            };
+
+            count(&prefix[..rounds.min(prefix.len())], 1)?;
+
+            if let Some(rest) = rounds.checked_sub(prefix.len()) {
+                count(cycle, rest / cycle.len())?;
+                count(&cycle[..rest % cycle.len()], 1)?;
+            }
+        }
+
+        game.monkey_business()
+    }
+
+    /// Follows an item that starts the next round with `monkey` on its own, as no other item
+    /// changes where it goes, until it starts a round as it did before. The metric of each round
+    /// is the monkeys that inspected the item in it.
+    ///
+    /// The item is followed for at most `MAX_ROUNDS` rounds, as every one of them is kept.
+    fn path(&self, monkey: usize, worry: u64) -> Result<Cycle<Vec<usize>>> {
+        const MAX_ROUNDS: usize = 100_000;
+
+        // A round starts with one of the monkeys and a worry level below the product of the
+        // divisors, except maybe the first, so the item repeats within as many rounds
+        let limit = usize::try_from(self.divisible_by_lcm)
+            .unwrap_or(usize::MAX)
+            .saturating_mul(self.monkeys.len())
+            .saturating_add(1)
+            .min(MAX_ROUNDS);
+
+        cycle::try_find(
+            &mut (monkey, worry, Vec::new()),
+            limit,
+            |(monkey, worry, inspections)| {
+                inspections.clear();
+
+                // As in a round of the game, a monkey further on inspects the item straight away
+                loop {
+                    inspections.push(*monkey);
+                    *worry = self.monkeys[*monkey].inspect(
+                        worry,
+                        Relief::Modulo,
+                        self.divisible_by_lcm,
+                    )?;
+
+                    let next = self.monkeys[*monkey].test(worry);
+                    let thrown_back = next < *monkey;
+                    *monkey = next;
+
+                    if thrown_back {
+                        return Ok(());
+                    }
+                }
+            },
+            |&(monkey, worry, _)| (monkey, worry),
+            |(_, _, inspections)| inspections.clone(),
+        )?
+        .ok_or_else(|| anyhow!("the item does not repeat within {limit} rounds"))
+    }
+}
+
+impl<W: Worry> Game<W> {
+    /// Plays `rounds` rounds, relieving worry after each inspection as told, and returns the
+    /// monkey business then.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a worry level or the monkey business overflows.
+    pub fn play(mut self, rounds: usize, relief: Relief) -> Result<usize> {
+        self.advance(rounds, relief)?;
+
+        self.monkey_business()
+    }
+
+    /// Plays `rounds` rounds, relieving worry after each inspection as told.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a worry level overflows.
+    pub fn advance(&mut self, rounds: usize, relief: Relief) -> Result<()> {
+        for _ in 0..rounds {
+            self.play_round(relief)?;
+        }
+
+        Ok(())
+    }
+
+    /// Starts recording every inspection of every item, from the next round on.
+    #[must_use]
+    pub fn track(mut self) -> Self {
+        let mut lineages = self
+            .items()
+            .map(|(_, item)| Lineage {
+                id: item.id,
+                hops: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        lineages.sort_unstable_by_key(|lineage| lineage.id);
+
+        self.lineages = Some(lineages);
+        self
+    }
+
+    /// Where the item with the given ID has been, if the game is being tracked.
+    #[must_use]
+    pub fn lineage(&self, id: usize) -> Option<&Lineage> {
+        self.lineages.as_ref()?.get(id)
+    }
+
+    /// The number of rounds played so far.
+    #[must_use]
+    pub const fn round(&self) -> usize {
+        self.round
+    }
+
+    /// Every item with the monkey that holds it, between rounds.
+    fn items(&self) -> impl Iterator<Item = (usize, &Item<W>)> {
+        self.monkeys
+            .iter()
+            .enumerate()
+            .flat_map(|(i, monkey)| monkey.items.iter().map(move |item| (i, item)))
+    }
+
+    fn play_round(&mut self, relief: Relief) -> Result<()> {
+        self.round += 1;
+
+        for i in 0..self.monkeys.len() {
+            while let Some(item) = self.monkeys[i].items.pop_front() {
+                let worry = self.monkeys[i].inspect(&item.worry, relief, self.divisible_by_lcm)?;
+                let j = self.monkeys[i].test(&worry);
+                self.monkeys[j].items.push_back(Item { id: item.id, worry });
+                self.inspections[i] += 1;
+
+                if let Some(lineages) = &mut self.lineages {
+                    lineages[item.id].hops.push(Hop {
+                        round: self.round,
+                        monkey: i,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn monkey_business(&self) -> Result<usize> {
+        const MOST_ACTIVE_MONKEYS: usize = 2;
+
+        let mut inspections = BinaryHeap::with_capacity(MOST_ACTIVE_MONKEYS + 1);
+        for &inspection in &self.inspections {
+            inspections.push(Reverse(inspection));
+
+            if inspections.len() > MOST_ACTIVE_MONKEYS {
+                inspections.pop();
+            }
+        }
+
+        inspections
+            .into_iter()
+            .try_fold(1_usize, |product, Reverse(inspection)| {
+                product
+                    .checked_mul(inspection)
+                    .ok_or_else(|| anyhow!("monkey business {product} * {inspection} overflows"))
+            })
+    }
+}
+
+impl Lineage {
+    #[must_use]
+    pub const fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Every inspection of the item, in order.
+    #[must_use]
+    pub fn hops(&self) -> &[Hop] {
+        &self.hops
+    }
+}
+
+impl Solution for Day11 {
+    type Input = Game;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Game::from_str(input)
+    }
+
+    fn part1(game: &Self::Input) -> Result<impl std::fmt::Display> {
+        game.clone().play(20, Relief::Divide)
+    }
+
+    fn part2(game: &Self::Input) -> Result<impl std::fmt::Display> {
+        game.clone().play(10_000, Relief::Modulo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact() {
+        let game = Day11::parse(include_str!("../in/example/day11.txt")).unwrap();
+
+        // Reducing modulo the product of the divisors throws every item the same way
+        for rounds in [1, 5, 10, 20] {
+            assert_eq!(
+                game.clone().play(rounds, Relief::Modulo).unwrap(),
+                game.exact().play(rounds, Relief::Exact).unwrap()
+            );
+        }
+
+        assert_eq!(
+            game.clone().play(20, Relief::Divide).unwrap(),
+            game.exact().play(20, Relief::Divide).unwrap()
+        );
+
+        // Without any relief, `old * old` overflows a `u64` within a few rounds
+        let err = game.play(15, Relief::Exact).unwrap_err();
+
+        assert!(err.to_string().ends_with("overflows"));
+    }
+
+    #[test]
+    fn lineage() {
+        let mut game = Day11::parse(include_str!("../in/example/day11.txt"))
+            .unwrap()
+            .track();
+        game.advance(1, Relief::Divide).unwrap();
+
+        // Monkey 0 throws 79 to monkey 3, which throws it to monkey 1 in the same round
+        assert_eq!(
+            game.lineage(0).unwrap().hops(),
+            [
+                Hop {
+                    round: 1,
+                    monkey: 0
+                },
+                Hop {
+                    round: 1,
+                    monkey: 3
+                },
+            ]
+        );
+        assert_eq!(game.lineage(10), None);
+        assert_eq!(
+            (0..10)
+                .map(|id| game.lineage(id).unwrap().hops().len())
+                .sum::<usize>(),
+            game.inspections.iter().sum()
+        );
+    }
+
+    #[test]
+    fn extrapolate() {
+        let game = Day11::parse(include_str!("../in/example/day11.txt")).unwrap();
+
+        assert_eq!(game.extrapolate(10_000).unwrap(), 2_713_310_158);
+
+        // Each monkey still counts its inspections, but their product no longer fits
+        let err = game.extrapolate(1_000_000_000_000).unwrap_err();
+        assert!(err.to_string().starts_with("monkey business"), "{err}");
+        assert!(err.to_string().ends_with("overflows"), "{err}");
+
+        // Between rounds, from anywhere in the game
+        let mut later = game.clone();
+        later.advance(7, Relief::Modulo).unwrap();
+
+        for rounds in [0, 1, 20, 1_000] {
+            assert_eq!(
+                later.extrapolate(rounds).unwrap(),
+                game.clone().play(7 + rounds, Relief::Modulo).unwrap()
+            );
+        }
+
+        let period = later.period(3).unwrap();
+
+        assert_eq!(
+            period,
+            Period {
+                start: 40,
+                length: 448
+            }
+        );
+        assert!(later.period(10).is_err());
+
+        // Item 0 only repeats after about a million rounds, far more than are followed
+        let slow = Day11::parse(
+            "Monkey 0:
+  Starting items: 1
+  Operation: new = old + 1
+  Test: divisible by 999983
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 2
+  Operation: new = old + 1
+  Test: divisible by 2
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            slow.period(0).unwrap_err().to_string(),
+            "the item does not repeat within 100000 rounds"
+        );
+
+        // Item 3 goes around the same monkeys in the same rounds, once it has started repeating
+        let mut tracked = later.track();
+        tracked
+            .advance(period.start + 2 * period.length - 7, Relief::Modulo)
+            .unwrap();
+
+        let hops = tracked.lineage(3).unwrap().hops();
+        let monkeys = |start: usize| {
+            hops.iter()
+                .filter(|hop| (start + 1..=start + period.length).contains(&hop.round))
+                .map(|hop| (hop.round - start, hop.monkey))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(monkeys(period.start), monkeys(period.start + period.length));
+        assert_eq!(tracked.round(), period.start + 2 * period.length);
+    }
+}
@@ -0,0 +1,105 @@
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use aoc_common::Grid;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use aoc_common::search;
+
+pub struct Day12;
+
+type Pos = aoc_common::Pos<usize>;
+
+#[derive(Debug)]
+pub struct HeightMap {
+    grid: Grid<char>,
+    start: Pos,
+    goal: Pos,
+}
+
+impl FromStr for HeightMap {
+    type Err = Error;
+
+    fn from_str(map: &str) -> Result<Self> {
+        let mut start = None;
+        let mut goal = None;
+
+        let grid = Grid::parse(map, |pos, tile| {
+            let (marker, height) = match tile {
+                "S" => (&mut start, 'a'),
+                "E" => (&mut goal, 'z'),
+                _ if tile.chars().all(|c| c.is_ascii_lowercase()) => {
+                    return Ok(parse::char(tile)?);
+                }
+                _ => return Err(ParseError::new(tile, "invalid elevation").into()),
+            };
+
+            if marker.replace(pos).is_some() {
+                return Err(ParseError::new(tile, "duplicate marker").into());
+            }
+
+            Ok(height)
+        })?;
+
+        let start = start.ok_or_else(|| ParseError::new(&map[map.len()..], "missing start 'S'"))?;
+        let goal = goal.ok_or_else(|| ParseError::new(&map[map.len()..], "missing goal 'E'"))?;
+
+        Ok(Self { grid, start, goal })
+    }
+}
+
+impl HeightMap {
+    fn get(&self, pos: Pos) -> Option<char> {
+        self.grid.get(pos).copied()
+    }
+
+    fn neighbors(
+        &self,
+        pos: Pos,
+        f: impl FnOnce((char, char)) -> bool + Copy,
+    ) -> impl Iterator<Item = Pos> {
+        self.grid
+            .neighbors(pos)
+            .filter(move |&adj| self.get(pos).zip(self.get(adj)).is_some_and(f))
+    }
+}
+
+fn steps(
+    map: &HeightMap,
+    start: Pos,
+    goal: impl Fn(Pos) -> bool,
+    neighbor: impl FnOnce((char, char)) -> bool + Copy,
+) -> Result<usize> {
+    search::bfs(start, |&pos| map.neighbors(pos, neighbor), |&pos| goal(pos))
+        .map(|path| path.cost())
+        .ok_or_else(|| anyhow!("no path from ({}, {})", start.x(), start.y()))
+}
+
+impl Solution for Day12 {
+    type Input = HeightMap;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        HeightMap::from_str(input)
+    }
+
+    fn part1(map: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::steps(
+            map,
+            map.start,
+            |pos| pos == map.goal,
+            |(tile, next)| (next as u8) <= (tile as u8) + 1,
+        )
+    }
+
+    fn part2(map: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::steps(
+            map,
+            map.goal,
+            |pos| map.get(pos).is_some_and(|tile| tile == 'a'),
+            |(tile, next)| tile as u8 <= next as u8 + 1,
+        )
+    }
+}
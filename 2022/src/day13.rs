@@ -1,21 +1,22 @@
 use std::cmp::Ordering;
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::anyhow;
-use anyhow::bail;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day13;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Val {
     Int(u8),
-    List(Vec<Val>),
+    List(Vec<Self>),
 }
 
-#[derive(Debug)]
-struct Pair(Val, Val);
+#[derive(Debug, PartialEq, Eq)]
+pub struct Pair(Val, Val);
 
 impl std::fmt::Display for Val {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -46,18 +47,27 @@ impl FromStr for Val {
     type Err = Error;
 
     fn from_str(val: &str) -> Result<Self> {
-        fn parse(input: &[u8], idx: &mut usize) -> Result<Val> {
+        // Packets are parsed, compared and dropped recursively, so bound the nesting to keep
+        // hostile input from overflowing the stack
+        const DEPTH_MAX: usize = 256;
+
+        fn parse(val: &str, idx: &mut usize, depth: usize) -> Result<Val> {
+            let input = val.as_bytes();
+
             match input.get(*idx) {
+                Some(b'[') if depth == DEPTH_MAX => {
+                    Err(ParseError::new(&val[*idx..=*idx], "packet is nested too deeply").into())
+                }
                 Some(b'[') => {
                     *idx += 1;
 
                     let mut list = Vec::new();
-                    while input[*idx] != b']' {
-                        if input[*idx] == b',' {
+                    while input.get(*idx) != Some(&b']') {
+                        if input.get(*idx) == Some(&b',') {
                             *idx += 1;
                         }
 
-                        list.push(parse(input, idx)?);
+                        list.push(parse(val, idx, depth + 1)?);
                     }
 
                     *idx += 1;
@@ -70,34 +80,47 @@ impl FromStr for Val {
                         *idx += 1;
                     }
 
-                    Ok(Val::Int(str::from_utf8(&input[start..*idx])?.parse()?))
+                    Ok(Val::Int(parse::from_str(&val[start..*idx])?))
                 }
-                _ => bail!(
-                    "invalid character at position {idx} in '{}'",
-                    str::from_utf8(input)?,
-                ),
+                None => Err(ParseError::new(&val[*idx..], "unexpected end of packet").into()),
+                _ => Err(self::unexpected(&val[*idx..]).into()),
             }
         }
 
-        parse(val.as_bytes(), &mut 0)
+        let mut idx = 0;
+        let packet = parse(val, &mut idx, 0)?;
+
+        if idx < val.len() {
+            return Err(self::unexpected(&val[idx..]).into());
+        }
+
+        Ok(packet)
     }
 }
 
+fn unexpected(rest: &str) -> ParseError {
+    ParseError::new(
+        parse::chars(rest).next().unwrap_or(rest),
+        "unexpected character",
+    )
+}
+
 impl FromStr for Pair {
     type Err = Error;
 
     fn from_str(pair: &str) -> Result<Self> {
         let (lhs, rhs) = pair
+            .trim_end()
             .split_once('\n')
-            .ok_or_else(|| anyhow!("invalid pair '{pair}'"))?;
+            .ok_or_else(|| ParseError::new(pair, "missing second packet"))?;
         let (lhs_val, rhs_val) = (lhs.parse()?, rhs.parse()?);
 
         if !matches!(lhs_val, Val::List(_)) {
-            bail!("invalid packet '{lhs}'");
+            return Err(ParseError::new(lhs, "packet is not a list").into());
         }
 
         if !matches!(rhs_val, Val::List(_)) {
-            bail!("invalid packet '{rhs}'");
+            return Err(ParseError::new(rhs, "packet is not a list").into());
         }
 
         Ok(Self(lhs_val, rhs_val))
@@ -145,15 +168,15 @@ fn part1(pairs: &[Pair]) -> usize {
         .sum()
 }
 
-fn part2(pairs: Vec<Pair>) -> usize {
+fn part2(pairs: &[Pair]) -> usize {
     let dividers = [
         Val::List(vec![Val::List(vec![Val::Int(2)])]),
         Val::List(vec![Val::List(vec![Val::Int(6)])]),
     ];
 
     let mut packets = pairs
-        .into_iter()
-        .flat_map(|Pair(a, b)| [a, b])
+        .iter()
+        .flat_map(|Pair(a, b)| [a.clone(), b.clone()])
         .chain(dividers.clone())
         .collect::<Vec<_>>();
 
@@ -167,29 +190,18 @@ fn part2(pairs: Vec<Pair>) -> usize {
         .product()
 }
 
-fn main() -> Result<()> {
-    let pairs = fs::read_to_string("in/day13.txt")?
-        .split("\n\n")
-        .map(Pair::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&pairs);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 5_625);
-    };
+impl Solution for Day13 {
+    type Input = Vec<Pair>;
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(pairs);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.split("\n\n").map(Pair::from_str).collect()
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 23_111);
-    };
+    fn part1(pairs: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(pairs))
+    }
 
-    Ok(())
+    fn part2(pairs: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(pairs))
+    }
 }
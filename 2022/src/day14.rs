@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use aoc_common::Grid;
+use aoc_common::Part;
+use aoc_common::Pos;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use aoc_common::render::Cell;
+use aoc_common::render::Frame;
+use aoc_common::render::Render;
+use aoc_common::visualize::Simulation;
+
+pub struct Day14;
+
+#[derive(Debug)]
+struct Path {
+    points: Vec<Pos<i32>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cave {
+    rocks: HashSet<Pos<i32>>,
+    sand: HashSet<Pos<i32>>,
+    x_min: i32,
+    x_max: i32,
+    y_min: i32,
+    y_max: i32,
+}
+
+/// Sand being poured into the cave, one grain moving one tile at a time.
+#[derive(Debug)]
+struct Pour {
+    cave: Cave,
+    /// Whether there is a floor two tiles below the lowest rock, rather than the abyss.
+    floor: bool,
+    grain: Option<Pos<i32>>,
+}
+
+/// Writes the rocks as paths, one per horizontal run of rock, which parse back into the same cave.
+impl std::fmt::Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rocks = self.rocks.iter().copied().collect::<Vec<_>>();
+        rocks.sort_unstable_by_key(|rock| (rock.y(), rock.x()));
+
+        for run in rocks.chunk_by(|a, b| a.y() == b.y() && a.x() + 1 == b.x()) {
+            let (start, end) = (run[0], run[run.len() - 1]);
+            writeln!(f, "{},{} -> {},{}", start.x(), start.y(), end.x(), end.y())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Pour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.cave.draw(f, self.grain, self.floor)
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let path = path.split(" -> ");
+
+        let mut points = Vec::with_capacity(path.clone().count());
+        for point in path {
+            let (x, y) = point
+                .split_once(',')
+                .ok_or_else(|| ParseError::new(point, "invalid point"))?;
+            let end = Pos::new(
+                parse::from_str::<u16>(x)?.into(),
+                parse::from_str::<u16>(y)?.into(),
+            );
+
+            if points
+                .last()
+                .is_some_and(|start: &Pos<i32>| start.x() != end.x() && start.y() != end.y())
+            {
+                return Err(ParseError::new(point, "path contains diagonal line").into());
+            }
+
+            points.push(end);
+        }
+
+        Ok(Self { points })
+    }
+}
+
+impl FromStr for Cave {
+    type Err = Error;
+
+    fn from_str(paths: &str) -> Result<Self> {
+        let paths = paths
+            .lines()
+            .map(Path::from_str)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::from(paths.as_slice()))
+    }
+}
+
+impl From<&[Path]> for Cave {
+    fn from(paths: &[Path]) -> Self {
+        let rocks = paths.iter().flat_map(Path::extend).collect::<HashSet<_>>();
+        let x_min = rocks.iter().copied().map(Pos::x).min().unwrap_or_default();
+        let x_max = rocks.iter().copied().map(Pos::x).max().unwrap_or_default();
+        let y_min = rocks.iter().copied().map(Pos::y).min().unwrap_or_default();
+        let y_max = rocks.iter().copied().map(Pos::y).max().unwrap_or_default();
+
+        Self {
+            rocks,
+            sand: HashSet::new(),
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+        }
+    }
+}
+
+impl Path {
+    fn extend(&self) -> impl Iterator<Item = Pos<i32>> + '_ {
+        self.points.windows(2).flat_map(|window| {
+            let (start, end) = (window[0], window[1]);
+            if start.x() == end.x() {
+                let (y_min, y_max) = (start.y().min(end.y()), start.y().max(end.y()));
+                Box::new((y_min..=y_max).map(move |y| Pos::new(start.x(), y)))
+                    as Box<dyn Iterator<Item = _>>
+            } else {
+                let (x_min, x_max) = (start.x().min(end.x()), start.x().max(end.x()));
+                Box::new((x_min..=x_max).map(move |x| Pos::new(x, start.y())))
+            }
+        })
+    }
+}
+
+impl Cave {
+    const SOURCE: Pos<i32> = Pos::new(500, 0);
+
+    fn is_air(&self, pos: Pos<i32>) -> bool {
+        !self.rocks.contains(&pos) && !self.sand.contains(&pos)
+    }
+
+    const fn is_abyss(&self, pos: Pos<i32>) -> bool {
+        pos.y() > self.y_max
+    }
+
+    const fn is_floor(&self, pos: Pos<i32>) -> bool {
+        pos.y() >= self.y_max + 2
+    }
+
+    /// Where a grain of sand at `sand` falls next, if it can fall at all.
+    fn fall(&self, sand: Pos<i32>, floor: bool) -> Option<Pos<i32>> {
+        [sand.down(), sand.down_left(), sand.down_right()]
+            .into_iter()
+            .find(|&next| self.is_air(next) && !(floor && self.is_floor(next)))
+    }
+
+    fn drop_sand(&mut self) -> bool {
+        let mut sand = Self::SOURCE;
+
+        while let Some(next) = self.fall(sand, false) {
+            if self.is_abyss(next) {
+                return false;
+            }
+
+            sand = next;
+        }
+
+        self.sand.insert(sand);
+        true
+    }
+
+    fn drop_sand_until_source_blocked(&mut self) -> bool {
+        let mut sand = Self::SOURCE;
+
+        while let Some(next) = self.fall(sand, true) {
+            sand = next;
+        }
+
+        self.sand.insert(sand);
+        sand != Self::SOURCE
+    }
+
+    /// The top left and bottom right corners of a drawing of the cave, including all the sand.
+    fn bounds(&self, grain: Option<Pos<i32>>, floor: bool) -> (Pos<i32>, Pos<i32>) {
+        let sand = self.sand.iter().copied().chain(grain);
+        let x_min = sand.clone().map(Pos::x).fold(self.x_min, i32::min);
+        let x_max = sand.map(Pos::x).fold(self.x_max, i32::max);
+        let y_min = self.y_min.min(Self::SOURCE.y());
+        let y_max = if floor { self.y_max + 2 } else { self.y_max };
+
+        (Pos::new(x_min, y_min), Pos::new(x_max, y_max))
+    }
+
+    fn draw(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        grain: Option<Pos<i32>>,
+        floor: bool,
+    ) -> std::fmt::Result {
+        let (min, max) = self.bounds(grain, floor);
+        let (x_min, y_min) = (min.x(), min.y());
+        let (x_max, y_max) = (max.x(), max.y());
+
+        for y in y_min..=y_max {
+            if y > y_min {
+                writeln!(f)?;
+            }
+
+            for x in x_min..=x_max {
+                let pos = Pos::new(x, y);
+                if Some(pos) == grain {
+                    write!(f, "~")?;
+                } else if self.rocks.contains(&pos) || (floor && self.is_floor(pos)) {
+                    write!(f, "#")?;
+                } else if self.sand.contains(&pos) {
+                    write!(f, "o")?;
+                } else if pos == Self::SOURCE {
+                    write!(f, "+")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn frame(&self, grain: Option<Pos<i32>>, floor: bool) -> Frame {
+        let (min, max) = self.bounds(grain, floor);
+        let world = |pos: Pos<i32>| Pos::new(i64::from(pos.x()), i64::from(pos.y()));
+        let width = max.x().abs_diff(min.x()) as usize + 1;
+        let height = max.y().abs_diff(min.y()) as usize + 1;
+
+        let mut frame = Frame::new(world(min), Grid::new(width, height, Cell::Empty));
+
+        if floor {
+            for x in min.x()..=max.x() {
+                frame.set(world(Pos::new(x, max.y())), Cell::Wall);
+            }
+        }
+
+        frame.set(world(Self::SOURCE), Cell::Marker);
+
+        for &rock in &self.rocks {
+            frame.set(world(rock), Cell::Wall);
+        }
+
+        for &sand in &self.sand {
+            frame.set(world(sand), Cell::Filled);
+        }
+
+        if let Some(grain) = grain {
+            frame.set(world(grain), Cell::Active);
+        }
+
+        frame
+    }
+}
+
+impl Render for Cave {
+    fn render(&self) -> Frame {
+        self.frame(None, false)
+    }
+}
+
+impl Render for Pour {
+    fn render(&self) -> Frame {
+        self.cave.frame(self.grain, self.floor)
+    }
+}
+
+impl Simulation for Pour {
+    fn step(&mut self) -> bool {
+        let Some(grain) = self.grain else {
+            if self.cave.sand.contains(&Cave::SOURCE) {
+                return false;
+            }
+
+            self.grain = Some(Cave::SOURCE);
+            return true;
+        };
+
+        match self.cave.fall(grain, self.floor) {
+            Some(next) if !self.floor && self.cave.is_abyss(next) => {
+                // The grain falls into the abyss, and so will every grain after it
+                self.grain = None;
+                false
+            }
+            Some(next) => {
+                self.grain = Some(next);
+                true
+            }
+            None => {
+                self.cave.sand.insert(grain);
+                self.grain = None;
+                true
+            }
+        }
+    }
+}
+
+fn part1(cave: &mut Cave) -> usize {
+    while cave.drop_sand() {}
+    cave.sand.len()
+}
+
+fn part2(cave: &mut Cave) -> usize {
+    while cave.drop_sand_until_source_blocked() {}
+    cave.sand.len()
+}
+
+impl Solution for Day14 {
+    type Input = Cave;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Cave::from_str(input)
+    }
+
+    fn part1(cave: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(&mut cave.clone()))
+    }
+
+    fn part2(cave: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(&mut cave.clone()))
+    }
+
+    fn simulate(cave: &Self::Input, part: Part) -> Option<Result<Box<dyn Simulation + '_>>> {
+        Some(Ok(Box::new(Pour {
+            cave: cave.clone(),
+            floor: part == Part::Two,
+            grain: None,
+        })))
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::bail;
+use aoc_common::Pos;
+use aoc_common::Solution;
+use aoc_common::parse::ParseError;
+
+pub struct Day15;
+
+#[derive(Debug)]
+pub struct Sensor {
+    pos: Pos<i64>,
+    beacon: Pos<i64>,
+}
+
+impl FromStr for Sensor {
+    type Err = Error;
+
+    fn from_str(sensor: &str) -> Result<Self> {
+        let end = &sensor[sensor.len()..];
+        let mut nums = sensor
+            .split(|c: char| !c.is_ascii_digit() && c != '-')
+            .flat_map(str::parse::<i32>)
+            .map(i64::from);
+
+        let pos = Pos::new(
+            nums.next()
+                .ok_or_else(|| ParseError::new(end, "missing sensor x-position"))?,
+            nums.next()
+                .ok_or_else(|| ParseError::new(end, "missing sensor y-position"))?,
+        );
+        let beacon = Pos::new(
+            nums.next()
+                .ok_or_else(|| ParseError::new(end, "missing beacon x-position"))?,
+            nums.next()
+                .ok_or_else(|| ParseError::new(end, "missing beacon y-position"))?,
+        );
+
+        Ok(Self { pos, beacon })
+    }
+}
+
+impl Sensor {
+    const fn dist_beacon(&self) -> i64 {
+        self.pos.manhattan(self.beacon)
+    }
+
+    const fn coverage_at_y(&self, y: i64) -> Option<(i64, i64)> {
+        let dist = self.dist_beacon();
+        let dy = (self.pos.y() - y).abs();
+        let remaining = dist - dy;
+
+        if remaining >= 0 {
+            Some((self.pos.x() - remaining, self.pos.x() + remaining))
+        } else {
+            None
+        }
+    }
+}
+
+fn merge(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    intervals.sort_unstable_by_key(|&(start, _)| start);
+
+    let mut merged = Vec::<(i64, i64)>::with_capacity(intervals.len());
+
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some((_, end_prev)) if start <= *end_prev => *end_prev = (*end_prev).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+fn part1(sensors: &[Sensor], y_target: i64) -> usize {
+    let intervals = self::merge(
+        sensors
+            .iter()
+            .filter_map(|sensor| sensor.coverage_at_y(y_target))
+            .collect(),
+    );
+
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    let coverage_at_y = intervals
+        .into_iter()
+        .map(|(start, end)| (end - start + 1) as usize)
+        .sum::<usize>();
+
+    let beacons_at_y = sensors
+        .iter()
+        .map(|sensor| sensor.beacon)
+        .filter(|beacon| beacon.y() == y_target)
+        .collect::<HashSet<_>>()
+        .len();
+
+    coverage_at_y - beacons_at_y
+}
+
+fn part2(sensors: &[Sensor], max: i64) -> Result<i64> {
+    const TUNING_FREQUENCY: i64 = 4_000_000;
+
+    for y in 0..=max {
+        let intervals = self::merge(
+            sensors
+                .iter()
+                .filter_map(|sensor| sensor.coverage_at_y(y))
+                .collect(),
+        );
+
+        let mut x = 0;
+        for (start, end) in intervals {
+            if start > x {
+                break;
+            }
+
+            x = x.max(end + 1);
+
+            if x > max {
+                break;
+            }
+        }
+
+        if x <= max {
+            return Ok(x * TUNING_FREQUENCY + y);
+        }
+    }
+
+    bail!("every position up to {max} is covered by a sensor")
+}
+
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(Sensor::from_str).collect()
+    }
+
+    fn part1(sensors: &Self::Input) -> Result<impl std::fmt::Display> {
+        const Y_TARGET: i64 = 2_000_000;
+        Ok(self::part1(sensors, Y_TARGET))
+    }
+
+    fn part2(sensors: &Self::Input) -> Result<impl std::fmt::Display> {
+        const MAX: i64 = 4_000_000;
+        self::part2(sensors, MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let sensors = Day15::parse(include_str!("../in/example/day15.txt")).unwrap();
+
+        assert_eq!(self::part1(&sensors, 10), 26);
+        assert_eq!(self::part2(&sensors, 20).unwrap(), 56_000_011);
+    }
+}
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
-use std::collections::VecDeque;
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
 use anyhow::anyhow;
+use anyhow::bail;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use aoc_common::search;
+
+pub struct Day16;
 
 #[derive(Clone, Debug)]
 struct Valve {
@@ -16,7 +20,7 @@ struct Valve {
 }
 
 #[derive(Debug)]
-struct Cave {
+pub struct Cave {
     start: usize,
     rates: Box<[u32]>,
     dist: Box<[Box<[u32]>]>,
@@ -26,19 +30,21 @@ impl FromStr for Valve {
     type Err = Error;
 
     fn from_str(valve: &str) -> Result<Self> {
-        let err = || anyhow!("invalid report '{valve}'");
-
-        let valve = valve.strip_prefix("Valve ").ok_or_else(err)?;
-        let (name, valve) = valve.split_once(" has flow rate=").ok_or_else(err)?;
-        let (rate, valve) = valve.split_once("; ").ok_or_else(err)?;
+        let valve = parse::strip_prefix(valve, "Valve ")?;
+        let (name, valve) = valve
+            .split_once(" has flow rate=")
+            .ok_or_else(|| ParseError::new(valve, "expected 'has flow rate='"))?;
+        let (rate, valve) = valve
+            .split_once("; ")
+            .ok_or_else(|| ParseError::new(valve, "expected ';'"))?;
         let tunnels = valve
             .strip_prefix("tunnel leads to valve ")
             .or_else(|| valve.strip_prefix("tunnels lead to valves "))
-            .ok_or_else(err)?;
+            .ok_or_else(|| ParseError::new(valve, "expected 'tunnels lead to valves'"))?;
 
         Ok(Self {
             name: name.to_string(),
-            rate: rate.parse()?,
+            rate: parse::from_str::<u16>(rate)?.into(),
             tunnels: tunnels.split(", ").map(str::to_string).collect(),
         })
     }
@@ -70,9 +76,17 @@ impl TryFrom<&[Valve]> for Cave {
 
             valves
                 .iter()
-                .map(|v| v.tunnels.iter().map(|t| idxs[t.as_str()]))
-                .map(Iterator::collect::<Vec<_>>)
-                .collect::<Vec<_>>()
+                .map(|v| {
+                    v.tunnels
+                        .iter()
+                        .map(|t| {
+                            idxs.get(t.as_str()).copied().ok_or_else(|| {
+                                anyhow!("valve '{}' leads to unknown valve '{t}'", v.name)
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?
         };
 
         let interesting = valves
@@ -82,6 +96,13 @@ impl TryFrom<&[Valve]> for Cave {
             .map(|(i, _)| i)
             .collect::<Vec<_>>();
 
+        if interesting.len() > 32 {
+            bail!(
+                "{} valves are worth visiting, but at most 32 are supported",
+                interesting.len()
+            );
+        }
+
         let start = interesting
             .iter()
             .position(|&i| valves[i].name == "AA")
@@ -92,22 +113,15 @@ impl TryFrom<&[Valve]> for Cave {
         let dist = interesting
             .iter()
             .map(|&src| {
-                let mut dist = vec![u32::MAX; valves.len()];
-                let mut queue = VecDeque::new();
-
-                dist[src] = 0;
-                queue.push_back(src);
-
-                while let Some(cur) = queue.pop_front() {
-                    for &next in &adj[cur] {
-                        if dist[next] == u32::MAX {
-                            dist[next] = dist[cur] + 1;
-                            queue.push_back(next);
-                        }
-                    }
-                }
-
-                interesting.iter().map(|&dst| dist[dst]).collect()
+                let dist = search::distances(src, |&cur| adj[cur].iter().copied());
+
+                interesting
+                    .iter()
+                    .map(|dst| {
+                        dist.get(dst)
+                            .map_or(u32::MAX, |&dist| u32::try_from(dist).unwrap_or(u32::MAX))
+                    })
+                    .collect()
             })
             .collect();
 
@@ -125,7 +139,7 @@ impl Cave {
                 continue;
             }
 
-            let time_to_next = self.dist[cur][next] + 1;
+            let time_to_next = self.dist[cur][next].saturating_add(1);
             if time_to_next >= time {
                 continue;
             }
@@ -171,26 +185,18 @@ fn part2(cave: &Cave) -> u32 {
     cave.max_pressure_with_elephant(TIME)
 }
 
-fn main() -> Result<()> {
-    let cave = Cave::from_str(&fs::read_to_string("in/day16.txt")?)?;
+impl Solution for Day16 {
+    type Input = Cave;
 
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&cave);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 1_880);
-    };
-
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&cave);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Cave::from_str(input)
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 2_520);
-    };
+    fn part1(cave: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(cave))
+    }
 
-    Ok(())
+    fn part2(cave: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(cave))
+    }
 }
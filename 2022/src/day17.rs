@@ -1,17 +1,27 @@
 use std::array;
-use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::collections::hash_map::Entry;
-use std::fs;
 use std::ops::Range;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
+use anyhow::anyhow;
 use anyhow::bail;
+use aoc_common::Grid;
+use aoc_common::Part;
+use aoc_common::Pos;
+use aoc_common::Solution;
+use aoc_common::cycle;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use aoc_common::render::Cell;
+use aoc_common::render::Frame;
+use aoc_common::render::Render;
+use aoc_common::visualize::Simulation;
+
+pub struct Day17;
 
 #[derive(Clone, Copy, Debug)]
-enum Jet {
+pub enum Jet {
     Left,
     Right,
 }
@@ -32,6 +42,16 @@ struct Chamber {
     rows: VecDeque<Row>,
 }
 
+/// Rocks falling into the chamber, one jet push and fall at a time.
+#[derive(Debug)]
+struct Tower<'a> {
+    chamber: Chamber,
+    jets: &'a [Jet],
+    jet: usize,
+    rocks: usize,
+    falling: Option<(Rock, usize)>,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct StateKey {
     rock: usize,
@@ -39,12 +59,6 @@ struct StateKey {
     rows: [Row; Self::LEN],
 }
 
-#[derive(Debug)]
-struct State {
-    rocks: usize,
-    height: usize,
-}
-
 impl std::fmt::Display for Jet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -85,23 +99,13 @@ impl std::fmt::Display for Rock {
 
 impl std::fmt::Display for Chamber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (i, &row) in self.rows.iter().enumerate() {
-            if i > 0 {
-                writeln!(f)?;
-            }
-
-            write!(f, "|")?;
-            for x in (0..Self::WIDTH).rev() {
-                let c = if row.bits & (1 << x) != 0 { '#' } else { '.' };
-                write!(f, "{c}")?;
-            }
-            write!(f, "|")?;
-        }
-
-        writeln!(f)?;
-        write!(f, "+{}+", "-".repeat(Self::WIDTH as usize))?;
+        self.draw(f, None)
+    }
+}
 
-        Ok(())
+impl std::fmt::Display for Tower<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.chamber.draw(f, self.falling)
     }
 }
 
@@ -266,24 +270,22 @@ impl Chamber {
         }
     }
 
-    #[allow(dead_code)]
-    fn display_with_rock(&self, rock: Rock, y: usize) -> String {
-        use std::fmt::Write;
-
-        let mut out = String::new();
-
+    fn draw(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        falling: Option<(Rock, usize)>,
+    ) -> std::fmt::Result {
         for (i, &row) in self.rows.iter().enumerate() {
             if i > 0 {
-                writeln!(out).unwrap();
+                writeln!(f)?;
             }
 
-            let rock_bits = if i >= y && i < y + rock.height {
-                rock.rows[i - y].bits
-            } else {
-                0
+            let rock_bits = match falling {
+                Some((rock, y)) if (y..y + rock.height).contains(&i) => rock.rows[i - y].bits,
+                _ => 0,
             };
 
-            write!(out, "|").unwrap();
+            write!(f, "|")?;
             for x in (0..Self::WIDTH).rev() {
                 let mask = 1 << x;
                 let c = if rock_bits & mask != 0 {
@@ -293,15 +295,49 @@ impl Chamber {
                 } else {
                     '.'
                 };
-                write!(out, "{c}").unwrap();
+                write!(f, "{c}")?;
             }
-            write!(out, "|").unwrap();
+            write!(f, "|")?;
         }
 
-        writeln!(out).unwrap();
-        write!(out, "+{}+", "-".repeat(Self::WIDTH as usize)).unwrap();
+        writeln!(f)?;
+        write!(f, "+{}+", "-".repeat(Self::WIDTH as usize))
+    }
+
+    /// Renders the top of the chamber, with its walls and the floor while it is in view. The
+    /// window follows the top of the tower as it grows, its bottom row staying put, so that the
+    /// frames keep the same size rather than growing with the tower.
+    fn frame(&self, falling: Option<(Rock, usize)>) -> Frame {
+        const WINDOW: usize = 48;
+
+        let width = usize::from(Self::WIDTH) + 2;
+        let height = (self.rows.len() + 1).min(WINDOW);
+        let mut cells = Grid::new(width, height, Cell::Wall);
+
+        for (i, &row) in self.rows.iter().enumerate().take(height) {
+            let rock_bits = match falling {
+                Some((rock, y)) if (y..y + rock.height).contains(&i) => rock.rows[i - y].bits,
+                _ => 0,
+            };
 
-        out
+            for x in 0..Self::WIDTH {
+                let mask = 1 << x;
+                let pos = Pos::new(width - 2 - usize::from(x), i);
+
+                cells[pos] = if rock_bits & mask != 0 {
+                    Cell::Active
+                } else if row.bits & mask != 0 {
+                    Cell::Filled
+                } else {
+                    Cell::Empty
+                };
+            }
+        }
+
+        Frame::new(
+            Pos::new(0, -i64::try_from(height).unwrap_or(i64::MAX)),
+            cells,
+        )
     }
 
     fn collides(&self, rock: Rock, y: usize) -> bool {
@@ -328,49 +364,88 @@ impl Chamber {
         self.rows.len()
     }
 
-    fn drop_rock(&mut self, mut rock: Rock, jets: &[Jet], jet: &mut usize) -> usize {
-        let gaps = 3 + rock.height;
-        for _ in 0..gaps {
+    /// The top rows of the chamber, padded with the floor when it is not yet that tall.
+    fn top(&self) -> [Row; StateKey::LEN] {
+        const FLOOR: Row = Row::new((1 << Chamber::WIDTH) - 1);
+
+        let mut rows = self.rows.iter().copied();
+        array::from_fn(|_| rows.next().unwrap_or(FLOOR))
+    }
+
+    /// Makes room for `rock` to appear three rows above the top of the tower, returning the row
+    /// it appears at.
+    fn spawn(&mut self, rock: Rock) -> usize {
+        for _ in 0..3 + rock.height {
             self.rows.push_front(Row::new(0));
         }
 
-        let mut y = 0;
+        0
+    }
 
-        // println!("Rock begins falling:");
-        // println!("{}\n", self.display_with_rock(rock, y));
+    /// Pushes the falling `rock` at row `y` with the next jet of gas and then lets it fall one
+    /// row, returning `false` once it has come to rest.
+    fn fall(&mut self, rock: &mut Rock, y: &mut usize, jets: &[Jet], jet: &mut usize) -> bool {
+        if let Some(shifted) = rock.shift(jets[*jet])
+            && !self.collides(shifted, *y)
+        {
+            *rock = shifted;
+        }
+        *jet = (*jet + 1) % jets.len();
 
-        loop {
-            let jet_dir = jets[*jet];
-            if let Some(shifted) = rock.shift(jet_dir)
-                && !self.collides(shifted, y)
-            {
-                // println!("Jet of gas pushes rock {jet_dir}:");
-                rock = shifted;
-            } else {
-                // println!("Jet of gas pushes rock {jet_dir}, but nothing happens:");
-            }
-            *jet = (*jet + 1) % jets.len();
-            // println!("{}\n", self.display_with_rock(rock, y));
-
-            if self.collides(rock, y + 1) {
-                self.settle(rock, y);
-                // println!("Rock comes to rest:");
-                // println!("{self}\n");
-                break;
+        if self.collides(*rock, *y + 1) {
+            self.settle(*rock, *y);
+
+            while self.rows.front().copied().is_some_and(Row::is_empty) {
+                self.rows.pop_front();
             }
-            y += 1;
-            // println!("Rock falls 1 unit:");
-            // println!("{}\n", self.display_with_rock(rock, y));
-        }
 
-        while self.rows.front().copied().is_some_and(Row::is_empty) {
-            self.rows.pop_front();
+            false
+        } else {
+            *y += 1;
+            true
         }
+    }
+
+    fn drop_rock(&mut self, mut rock: Rock, jets: &[Jet], jet: &mut usize) -> usize {
+        let mut y = self.spawn(rock);
+        while self.fall(&mut rock, &mut y, jets, jet) {}
 
         self.height()
     }
 }
 
+impl Render for Chamber {
+    fn render(&self) -> Frame {
+        self.frame(None)
+    }
+}
+
+impl Render for Tower<'_> {
+    fn render(&self) -> Frame {
+        self.chamber.frame(self.falling)
+    }
+}
+
+impl Simulation for Tower<'_> {
+    fn step(&mut self) -> bool {
+        const ROCKS: usize = 2022;
+
+        if let Some((rock, y)) = &mut self.falling {
+            if !self.chamber.fall(rock, y, self.jets, &mut self.jet) {
+                self.falling = None;
+                self.rocks += 1;
+            }
+        } else if self.rocks < ROCKS {
+            let rock = Rock::VARIANTS[self.rocks % Rock::VARIANTS.len()];
+            self.falling = Some((rock, self.chamber.spawn(rock)));
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
 impl StateKey {
     const LEN: usize = 30;
 }
@@ -388,91 +463,63 @@ fn part1(jets: &[Jet]) -> usize {
     chamber.height()
 }
 
-fn part2(jets: &[Jet]) -> usize {
+fn part2(jets: &[Jet]) -> Result<usize> {
     const ROCKS: usize = 1_000_000_000_000;
 
-    let mut state = HashMap::new();
-    let mut chamber = Chamber::new();
-    let mut jet = 0;
-
-    for (count, idx, rock) in Rock::VARIANTS
-        .into_iter()
-        .enumerate()
-        .cycle()
-        .take(ROCKS)
-        .enumerate()
-        .map(|(count, (idx, rock))| (count + 1, idx, rock))
-    {
-        let height = chamber.drop_rock(rock, jets, &mut jet);
-
-        if height >= StateKey::LEN {
-            let mut rows = chamber.rows.iter().copied();
-
-            match state.entry(StateKey {
-                rock: idx,
-                jet,
-                rows: array::from_fn(|_| rows.next().unwrap()),
-            }) {
-                Entry::Vacant(vacant) => {
-                    vacant.insert(State {
-                        rocks: count,
-                        height,
-                    });
-                }
-                Entry::Occupied(occupied) => {
-                    let &State {
-                        rocks: rocks_first_seen,
-                        height: height_first_seen,
-                    } = occupied.get();
-
-                    let cycle_rocks = count - rocks_first_seen;
-                    let cycle_height = height - height_first_seen;
-
-                    let remaining = ROCKS - count;
-                    let full_cycles = remaining / cycle_rocks;
-                    let leftover = remaining % cycle_rocks;
-
-                    for rock in Rock::VARIANTS
-                        .into_iter()
-                        .cycle()
-                        .skip(idx + 1)
-                        .take(leftover)
-                    {
-                        chamber.drop_rock(rock, jets, &mut jet);
-                    }
-
-                    return chamber.height() + (full_cycles * cycle_height);
-                }
-            }
-        }
+    let mut state = (Chamber::new(), 0, 0);
+    let cycle = cycle::find(
+        &mut state,
+        ROCKS,
+        |(chamber, jet, rocks)| {
+            chamber.drop_rock(Rock::VARIANTS[*rocks % Rock::VARIANTS.len()], jets, jet);
+            *rocks += 1;
+        },
+        |&(ref chamber, jet, rocks)| StateKey {
+            rock: rocks % Rock::VARIANTS.len(),
+            jet,
+            rows: chamber.top(),
+        },
+        |(chamber, ..)| chamber.height(),
+    );
+
+    match cycle {
+        Some(cycle) => cycle
+            .extrapolate(ROCKS)
+            .ok_or_else(|| anyhow!("the tower grows too tall")),
+        None => Ok(state.0.height()),
     }
-
-    chamber.height()
 }
 
-fn main() -> Result<()> {
-    let jets = fs::read_to_string("in/day17.txt")?
-        .chars()
-        .map(Jet::try_from)
-        .collect::<Result<Vec<_>>>()?;
+impl Solution for Day17 {
+    type Input = Vec<Jet>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let jets = input.trim_end();
 
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&jets);
-        let elapsed = Instant::now().duration_since(start);
+        if jets.is_empty() {
+            return Err(ParseError::new(jets, "no jets of gas").into());
+        }
 
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 3_232);
-    };
+        Ok(parse::chars(jets)
+            .map(parse::char)
+            .collect::<Result<_, _>>()?)
+    }
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&jets);
-        let elapsed = Instant::now().duration_since(start);
+    fn part1(jets: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(jets))
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 1_585_632_183_915);
-    };
+    fn part2(jets: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part2(jets)
+    }
 
-    Ok(())
+    fn simulate(jets: &Self::Input, _: Part) -> Option<Result<Box<dyn Simulation + '_>>> {
+        Some(Ok(Box::new(Tower {
+            chamber: Chamber::new(),
+            jets,
+            jet: 0,
+            rocks: 0,
+            falling: None,
+        })))
+    }
 }
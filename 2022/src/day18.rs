@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use anyhow::Ok;
+use anyhow::Result;
+use aoc_common::Pos3;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use aoc_common::search;
+
+pub struct Day18;
+
+// Parsed as `i8`, leaving room to step around the droplet without overflowing
+type Cube = Pos3<i16>;
+
+fn cube(cube: &str) -> Result<Cube> {
+    let err = || ParseError::new(&cube[cube.len()..], "missing coordinate");
+    let mut coords = cube.split(',').map(parse::from_str::<i8>);
+
+    Ok(Cube::new(
+        coords.next().ok_or_else(err)??.into(),
+        coords.next().ok_or_else(err)??.into(),
+        coords.next().ok_or_else(err)??.into(),
+    ))
+}
+
+fn part1(cubes: &[Cube]) -> usize {
+    let mut surface_area = 6 * cubes.len();
+
+    let cubes = cubes.iter().copied().collect::<HashSet<_>>();
+    for &cube in &cubes {
+        surface_area -= cube
+            .adj()
+            .filter(|neighbor| cubes.contains(neighbor))
+            .count();
+    }
+
+    surface_area
+}
+
+fn part2(cubes: &[Cube]) -> usize {
+    fn axis_min(cubes: &[Cube], f: impl FnMut(Cube) -> i16) -> i16 {
+        cubes.iter().copied().map(f).min().unwrap_or_default()
+    }
+
+    fn axis_max(cubes: &[Cube], f: impl FnMut(Cube) -> i16) -> i16 {
+        cubes.iter().copied().map(f).max().unwrap_or_default()
+    }
+
+    let x_min = axis_min(cubes, Cube::x) - 1;
+    let y_min = axis_min(cubes, Cube::y) - 1;
+    let z_min = axis_min(cubes, Cube::z) - 1;
+    let x_max = axis_max(cubes, Cube::x) + 1;
+    let y_max = axis_max(cubes, Cube::y) + 1;
+    let z_max = axis_max(cubes, Cube::z) + 1;
+
+    let cubes = cubes.iter().copied().collect::<HashSet<_>>();
+    let in_bounds = |pos: Cube| {
+        (x_min..=x_max).contains(&pos.x())
+            && (y_min..=y_max).contains(&pos.y())
+            && (z_min..=z_max).contains(&pos.z())
+    };
+
+    // Flood the air around the droplet, which then touches every face on its outside
+    let air = search::distances(Cube::new(x_min, y_min, z_min), |&pos| {
+        pos.adj()
+            .filter(|&adj| in_bounds(adj) && !cubes.contains(&adj))
+    });
+
+    air.keys()
+        .flat_map(|pos| pos.adj())
+        .filter(|adj| cubes.contains(adj))
+        .count()
+}
+
+impl Solution for Day18 {
+    type Input = Vec<Cube>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(self::cube).collect()
+    }
+
+    fn part1(cubes: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(cubes))
+    }
+
+    fn part2(cubes: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(cubes))
+    }
+}
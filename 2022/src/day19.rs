@@ -1,13 +1,14 @@
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::anyhow;
+use aoc_common::Solution;
+use aoc_common::parse::ParseError;
+
+pub struct Day19;
 
 #[derive(Debug)]
-struct Blueprint {
+pub struct Blueprint {
     id: u8,
     cost_ore_robot_ore: u8,
     cost_clay_robot_ore: u8,
@@ -35,24 +36,26 @@ impl FromStr for Blueprint {
     type Err = Error;
 
     fn from_str(blueprint: &str) -> Result<Self> {
+        let end = &blueprint[blueprint.len()..];
         let mut nums = blueprint
             .split(|c: char| !c.is_ascii_digit())
             .flat_map(str::parse);
 
         Ok(Self::new(
-            nums.next().ok_or_else(|| anyhow!("missing blueprint ID"))?,
             nums.next()
-                .ok_or_else(|| anyhow!("missing ore robot cost"))?,
+                .ok_or_else(|| ParseError::new(end, "missing blueprint ID"))?,
+            nums.next()
+                .ok_or_else(|| ParseError::new(end, "missing ore robot cost"))?,
             nums.next()
-                .ok_or_else(|| anyhow!("missing clay robot cost"))?,
+                .ok_or_else(|| ParseError::new(end, "missing clay robot cost"))?,
             nums.next()
-                .ok_or_else(|| anyhow!("missing obsidian robot ore cost"))?,
+                .ok_or_else(|| ParseError::new(end, "missing obsidian robot ore cost"))?,
             nums.next()
-                .ok_or_else(|| anyhow!("missing obsidian robot clay cost"))?,
+                .ok_or_else(|| ParseError::new(end, "missing obsidian robot clay cost"))?,
             nums.next()
-                .ok_or_else(|| anyhow!("missing geode robot ore cost"))?,
+                .ok_or_else(|| ParseError::new(end, "missing geode robot ore cost"))?,
             nums.next()
-                .ok_or_else(|| anyhow!("missing geode robot obsidian cost"))?,
+                .ok_or_else(|| ParseError::new(end, "missing geode robot obsidian cost"))?,
         ))
     }
 }
@@ -283,29 +286,18 @@ fn part2(blueprints: &[Blueprint]) -> u16 {
         .product()
 }
 
-fn main() -> Result<()> {
-    let blueprints = fs::read_to_string("in/day19.txt")?
-        .lines()
-        .map(Blueprint::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&blueprints);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 1_650);
-    };
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&blueprints);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(Blueprint::from_str).collect()
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 5_824);
-    };
+    fn part1(blueprints: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(blueprints))
+    }
 
-    Ok(())
+    fn part2(blueprints: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(blueprints))
+    }
 }
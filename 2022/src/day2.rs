@@ -1,11 +1,13 @@
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::anyhow;
 use anyhow::bail;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day2;
 
 #[derive(Clone, Copy, Debug)]
 enum Shape {
@@ -22,7 +24,7 @@ enum Outcome {
 }
 
 #[derive(Debug)]
-struct StrategyGuide {
+pub struct StrategyGuide {
     rounds: Vec<(Shape, Shape)>,
     desired_outcomes: Vec<(Shape, Outcome)>,
 }
@@ -65,20 +67,11 @@ impl FromStr for StrategyGuide {
         for round in guide.lines() {
             let (opponent, lhs) = round
                 .split_once(' ')
-                .ok_or_else(|| anyhow!("invalid round '{round}'"))?;
-
-            let opponent = (opponent.len() == 1)
-                .then_some(opponent.chars().next())
-                .flatten()
-                .map(Shape::try_from)
-                .ok_or_else(|| anyhow!("invalid opponent strategy '{opponent}'"))??;
-            let lhs = (lhs.len() == 1)
-                .then_some(lhs.chars().next())
-                .flatten()
-                .ok_or_else(|| anyhow!("invalid LHS '{lhs}'"))?;
+                .ok_or_else(|| ParseError::new(round, "invalid round"))?;
 
-            let me = Shape::try_from(lhs)?;
-            let outcome = Outcome::try_from(lhs)?;
+            let opponent = parse::char::<Shape>(opponent)?;
+            let me = parse::char::<Shape>(lhs)?;
+            let outcome = parse::char::<Outcome>(lhs)?;
 
             rounds.push((opponent, me));
             desired_outcomes.push((opponent, outcome));
@@ -150,26 +143,18 @@ fn part2(guide: &StrategyGuide) -> u64 {
         .sum()
 }
 
-fn main() -> Result<()> {
-    let guide = StrategyGuide::from_str(&fs::read_to_string("in/day2.txt")?)?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&guide);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 12_156);
-    };
+impl Solution for Day2 {
+    type Input = StrategyGuide;
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&guide);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        StrategyGuide::from_str(input)
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 10_835);
-    };
+    fn part1(guide: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(guide))
+    }
 
-    Ok(())
+    fn part2(guide: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(guide))
+    }
 }
@@ -0,0 +1,111 @@
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use aoc_common::Solution;
+use aoc_common::parse;
+
+pub struct Day20;
+
+#[derive(Clone, Debug)]
+pub struct EncryptedFile {
+    vals: Vec<i64>,
+}
+
+impl FromStr for EncryptedFile {
+    type Err = Error;
+
+    fn from_str(file: &str) -> Result<Self> {
+        Ok(Self {
+            vals: file
+                .lines()
+                .map(parse::from_str)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl EncryptedFile {
+    fn mix(self, rounds: usize) -> Result<Self> {
+        if self.vals.len() < 2 {
+            return Ok(self);
+        }
+
+        let mut idxs = (0..self.vals.len()).collect::<Vec<_>>();
+        let len = (idxs.len() - 1).cast_signed();
+
+        for _ in 0..rounds {
+            for i in 0..self.vals.len() {
+                let Some(idx) = idxs.iter().position(|&idx| idx == i) else {
+                    continue;
+                };
+                idxs.remove(idx);
+
+                let idx_new =
+                    (isize::try_from(self.vals[i])?.rem_euclid(len) + idx.cast_signed()) % len;
+                idxs.insert(idx_new.cast_unsigned(), i);
+            }
+        }
+
+        Ok(Self {
+            vals: idxs.into_iter().map(|i| self.vals[i]).collect(),
+        })
+    }
+
+    fn grove_coords(&self) -> Result<[i64; 3]> {
+        let idx_zero = self
+            .vals
+            .iter()
+            .position(|&val| val == 0)
+            .ok_or_else(|| anyhow!("the file contains no 0"))?;
+
+        Ok([1_000, 2_000, 3_000]
+            .map(|idx| (idx + idx_zero) % self.vals.len())
+            .map(|idx| self.vals[idx]))
+    }
+}
+
+fn part1(file: EncryptedFile) -> Result<i64> {
+    self::sum(file.mix(1)?.grove_coords()?)
+}
+
+fn part2(file: EncryptedFile) -> Result<i64> {
+    const DECRYPTION_KEY: i64 = 811_589_153;
+
+    let file = EncryptedFile {
+        vals: file
+            .vals
+            .into_iter()
+            .map(|val| {
+                val.checked_mul(DECRYPTION_KEY)
+                    .ok_or_else(|| anyhow!("{val} is too large to decrypt"))
+            })
+            .collect::<Result<_>>()?,
+    };
+
+    self::sum(file.mix(10)?.grove_coords()?)
+}
+
+fn sum(coords: [i64; 3]) -> Result<i64> {
+    coords
+        .into_iter()
+        .try_fold(0_i64, i64::checked_add)
+        .ok_or_else(|| anyhow!("the sum of the grove coordinates {coords:?} overflows"))
+}
+
+impl Solution for Day20 {
+    type Input = EncryptedFile;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        EncryptedFile::from_str(input)
+    }
+
+    fn part1(file: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part1(file.clone())
+    }
+
+    fn part2(file: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part2(file.clone())
+    }
+}
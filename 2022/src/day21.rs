@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day21;
+
+#[derive(Debug)]
+pub enum Job {
+    Num(i64),
+    Operation {
+        lhs: String,
+        rhs: String,
+        op: Operator,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug)]
+struct Monkey {
+    name: String,
+    job: Job,
+}
+
+impl FromStr for Job {
+    type Err = Error;
+
+    fn from_str(job: &str) -> Result<Self> {
+        if let Ok(num) = job.parse() {
+            Ok(Self::Num(num))
+        } else {
+            let parts = job.split_ascii_whitespace().collect::<Vec<_>>();
+            let &[lhs, op, rhs] = parts.as_slice() else {
+                return Err(ParseError::new(job, "invalid job").into());
+            };
+
+            Ok(Self::Operation {
+                lhs: lhs.to_string(),
+                rhs: rhs.to_string(),
+                op: parse::char(op)?,
+            })
+        }
+    }
+}
+
+impl TryFrom<char> for Operator {
+    type Error = Error;
+
+    fn try_from(operator: char) -> Result<Self> {
+        match operator {
+            '+' => Ok(Self::Add),
+            '-' => Ok(Self::Sub),
+            '*' => Ok(Self::Mul),
+            '/' => Ok(Self::Div),
+            _ => bail!("invalid operator '{operator}'"),
+        }
+    }
+}
+
+impl FromStr for Monkey {
+    type Err = Error;
+
+    fn from_str(monkey: &str) -> Result<Self> {
+        let (name, job) = monkey
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(monkey, "invalid monkey"))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            job: job.parse()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Add => write!(f, "+"),
+            Self::Sub => write!(f, "-"),
+            Self::Mul => write!(f, "*"),
+            Self::Div => write!(f, "/"),
+        }
+    }
+}
+
+impl Operator {
+    fn eval(self, lhs: i64, rhs: i64) -> Result<i64> {
+        match self {
+            Self::Add => lhs.checked_add(rhs),
+            Self::Sub => lhs.checked_sub(rhs),
+            Self::Mul => lhs.checked_mul(rhs),
+            Self::Div => lhs.checked_div(rhs),
+        }
+        .ok_or_else(|| anyhow!("cannot evaluate {lhs} {self} {rhs}"))
+    }
+}
+
+/// Checks that monkeys only wait on known monkeys, and never on themselves, so that every job can
+/// be evaluated.
+fn validate(jobs: &HashMap<String, Job>) -> Result<()> {
+    // Jobs are evaluated recursively, so bound how long a chain of monkeys waiting on each other
+    // can get before it would overflow the stack
+    const DEPTH_MAX: usize = 1_000;
+
+    let mut depths = HashMap::<&str, usize>::new();
+    let mut dependents = HashMap::<&str, Vec<&str>>::new();
+    let mut pending = HashMap::<&str, usize>::new();
+    let mut ready = Vec::new();
+
+    for (monkey, job) in jobs {
+        match job {
+            Job::Num(_) => ready.push(monkey.as_str()),
+            Job::Operation { lhs, rhs, .. } => {
+                for dependency in [lhs, rhs] {
+                    if !jobs.contains_key(dependency) {
+                        bail!("monkey '{monkey}' waits on unknown monkey '{dependency}'");
+                    }
+
+                    dependents.entry(dependency).or_default().push(monkey);
+                }
+
+                pending.insert(monkey, 2);
+            }
+        }
+    }
+
+    let mut evaluated = 0;
+
+    while let Some(monkey) = ready.pop() {
+        let depth = depths.get(monkey).copied().unwrap_or_default();
+        evaluated += 1;
+
+        if depth > DEPTH_MAX {
+            bail!("monkey '{monkey}' waits on a chain of more than {DEPTH_MAX} monkeys");
+        }
+
+        for &dependent in dependents.get(monkey).into_iter().flatten() {
+            let depth_dependent = depths.entry(dependent).or_default();
+            *depth_dependent = (*depth_dependent).max(depth + 1);
+
+            if let Some(count) = pending.get_mut(dependent) {
+                *count -= 1;
+
+                if *count == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+    }
+
+    if evaluated < jobs.len() {
+        bail!("monkeys wait on each other in a cycle");
+    }
+
+    Ok(())
+}
+
+fn root(jobs: &HashMap<String, Job>) -> Result<(&str, &str)> {
+    match jobs.get("root") {
+        Some(Job::Operation { lhs, rhs, .. }) => Ok((lhs, rhs)),
+        Some(Job::Num(_)) => bail!("monkey 'root' yells a number"),
+        None => bail!("missing monkey 'root'"),
+    }
+}
+
+fn dfs<'a>(
+    jobs: &'a HashMap<String, Job>,
+    monkey: &'a str,
+    cache: &mut HashMap<&'a str, i64>,
+) -> Result<i64> {
+    if let Some(&num) = cache.get(monkey) {
+        return Ok(num);
+    }
+
+    let num = match &jobs[monkey] {
+        &Job::Num(num) => num,
+        Job::Operation { lhs, rhs, op } => {
+            op.eval(self::dfs(jobs, lhs, cache)?, self::dfs(jobs, rhs, cache)?)?
+        }
+    };
+
+    cache.insert(monkey, num);
+    Ok(num)
+}
+
+fn part1(jobs: &HashMap<String, Job>) -> Result<i64> {
+    let mut cache = HashMap::new();
+
+    self::root(jobs)?;
+    self::dfs(jobs, "root", &mut cache)
+}
+
+fn part2(jobs: &HashMap<String, Job>) -> Result<i64> {
+    fn is_dependent_on_humn<'a>(
+        jobs: &'a HashMap<String, Job>,
+        monkey: &'a str,
+        cache: &mut HashMap<&'a str, bool>,
+    ) -> bool {
+        if let Some(&dependent) = cache.get(monkey) {
+            return dependent;
+        }
+
+        if monkey == "humn" {
+            return true;
+        }
+
+        let dependent = match &jobs[monkey] {
+            Job::Num(_) => false,
+            Job::Operation { lhs, rhs, .. } => {
+                is_dependent_on_humn(jobs, lhs, cache) || is_dependent_on_humn(jobs, rhs, cache)
+            }
+        };
+
+        cache.insert(monkey, dependent);
+        dependent
+    }
+
+    fn dfs_inverse<'a>(
+        jobs: &'a HashMap<String, Job>,
+        monkey: &'a str,
+        target: i64,
+        cache_dependent: &mut HashMap<&'a str, bool>,
+        cache_dfs: &mut HashMap<&'a str, i64>,
+    ) -> Result<i64> {
+        if monkey == "humn" {
+            return Ok(target);
+        }
+
+        let Job::Operation { lhs, rhs, op } = &jobs[monkey] else {
+            bail!("monkey '{monkey}' does not depend on 'humn'");
+        };
+
+        let dependent = if cache_dependent
+            .get(lhs.as_str())
+            .is_some_and(|&dependent| dependent)
+        {
+            lhs
+        } else if cache_dependent
+            .get(rhs.as_str())
+            .is_some_and(|&dependent| dependent)
+        {
+            rhs
+        } else if is_dependent_on_humn(jobs, lhs, cache_dependent) {
+            lhs
+        } else if is_dependent_on_humn(jobs, rhs, cache_dependent) {
+            rhs
+        } else {
+            bail!("monkey '{monkey}' does not depend on 'humn'");
+        };
+
+        let target_new = if dependent == lhs {
+            let rhs = self::dfs(jobs, rhs, cache_dfs)?;
+            match op {
+                Operator::Add => Operator::Sub.eval(target, rhs),
+                Operator::Sub => Operator::Add.eval(target, rhs),
+                Operator::Mul => Operator::Div.eval(target, rhs),
+                Operator::Div => Operator::Mul.eval(target, rhs),
+            }
+        } else {
+            let lhs = self::dfs(jobs, lhs, cache_dfs)?;
+            match op {
+                Operator::Add => Operator::Sub.eval(target, lhs),
+                Operator::Sub => Operator::Sub.eval(lhs, target),
+                Operator::Mul => Operator::Div.eval(target, lhs),
+                Operator::Div => Operator::Div.eval(lhs, target),
+            }
+        }?;
+
+        dfs_inverse(jobs, dependent, target_new, cache_dependent, cache_dfs)
+    }
+
+    let (lhs, rhs) = self::root(jobs)?;
+
+    let mut cache_dependent = HashMap::new();
+    let mut cache_dfs = HashMap::new();
+
+    let (dependent, target) = if is_dependent_on_humn(jobs, lhs, &mut cache_dependent) {
+        (lhs, self::dfs(jobs, rhs, &mut cache_dfs)?)
+    } else if is_dependent_on_humn(jobs, rhs, &mut cache_dependent) {
+        (rhs, self::dfs(jobs, lhs, &mut cache_dfs)?)
+    } else {
+        bail!("monkey 'root' does not depend on 'humn'");
+    };
+
+    dfs_inverse(
+        jobs,
+        dependent,
+        target,
+        &mut cache_dependent,
+        &mut cache_dfs,
+    )
+}
+
+impl Solution for Day21 {
+    type Input = HashMap<String, Job>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let jobs = input
+            .lines()
+            .map(Monkey::from_str)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(|monkey| (monkey.name, monkey.job))
+            .collect();
+
+        self::validate(&jobs)?;
+
+        Ok(jobs)
+    }
+
+    fn part1(jobs: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part1(jobs)
+    }
+
+    fn part2(jobs: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part2(jobs)
+    }
+}
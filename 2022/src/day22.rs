@@ -1,109 +1,81 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fmt::Write;
-use std::fs;
 use std::ops::Range;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
 use anyhow::anyhow;
 use anyhow::bail;
+use aoc_common::Axis;
+use aoc_common::Grid;
+use aoc_common::Part;
+use aoc_common::Pos;
+use aoc_common::Pos3;
+use aoc_common::Rotation;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use aoc_common::render::Cell;
+use aoc_common::render::Frame;
+use aoc_common::render::Render;
+use aoc_common::visualize::Simulation;
+
+pub struct Day22;
+
+type Vec3 = Pos3<i32>;
+
+/// How a face of the board sits on the cube, as the rotation taking the first face's `right`
+/// (`X`), `down` (`Y`) and outward `normal` (`Z`) onto this face's.
+#[derive(Clone, Copy, Debug)]
+struct FaceOrientation(Rotation);
 
-use aoc_2022::Pos;
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Vec3([i32; 3]);
-
-const PX: Vec3 = Vec3([1, 0, 0]);
-const PY: Vec3 = Vec3([0, 1, 0]);
-const PZ: Vec3 = Vec3([0, 0, 1]);
-
-impl Vec3 {
-    const fn cross(self, other: Self) -> Self {
-        Self([
-            self.0[1] * other.0[2] - self.0[2] * other.0[1],
-            self.0[2] * other.0[0] - self.0[0] * other.0[2],
-            self.0[0] * other.0[1] - self.0[1] * other.0[0],
-        ])
+impl FaceOrientation {
+    fn normal(self) -> Vec3 {
+        self.0 * Vec3::Z
     }
-}
-
-impl std::ops::Neg for Vec3 {
-    type Output = Self;
 
-    fn neg(self) -> Self {
-        Self([-self.0[0], -self.0[1], -self.0[2]])
+    fn right(self) -> Vec3 {
+        self.0 * Vec3::X
     }
-}
-
-impl std::ops::Add for Vec3 {
-    type Output = Self;
 
-    fn add(self, rhs: Self) -> Self {
-        Self([
-            self.0[0] + rhs.0[0],
-            self.0[1] + rhs.0[1],
-            self.0[2] + rhs.0[2],
-        ])
+    fn down(self) -> Vec3 {
+        self.0 * Vec3::Y
     }
-}
-
-#[derive(Clone, Copy, Debug)]
-struct FaceOrientation {
-    normal: Vec3,
-    right: Vec3,
-    down: Vec3,
-}
 
-impl FaceOrientation {
+    /// The orientation of the face next to this one in `grid_dir`, folded over the shared edge.
     fn fold(self, grid_dir: Direction) -> Self {
-        let o = match grid_dir {
-            Direction::Right => Self {
-                normal: self.right,
-                right: -self.normal,
-                down: self.down,
-            },
-            Direction::Left => Self {
-                normal: -self.right,
-                right: self.normal,
-                down: self.down,
-            },
-            Direction::Down => Self {
-                normal: self.down,
-                right: self.right,
-                down: -self.normal,
-            },
-            Direction::Up => Self {
-                normal: -self.down,
-                right: self.right,
-                down: self.normal,
-            },
+        let turn = match grid_dir {
+            Direction::Right => Rotation::about(Axis::Y, 1),
+            Direction::Left => Rotation::about(Axis::Y, -1),
+            Direction::Down => Rotation::about(Axis::X, -1),
+            Direction::Up => Rotation::about(Axis::X, 1),
         };
+        let o = Self(self.0 * turn);
 
-        debug_assert_eq!(o.normal, o.right.cross(o.down));
+        debug_assert_eq!(o.normal(), o.right().cross(o.down()));
         o
     }
 
     fn tangent(self, dir: Direction) -> Vec3 {
         match dir {
-            Direction::Up => -self.down,
-            Direction::Down => self.down,
-            Direction::Left => -self.right,
-            Direction::Right => self.right,
+            Direction::Up => -self.down(),
+            Direction::Down => self.down(),
+            Direction::Left => -self.right(),
+            Direction::Right => self.right(),
         }
     }
 
-    const fn along_edge(self, dir: Direction) -> Vec3 {
+    fn along_edge(self, dir: Direction) -> Vec3 {
         match dir {
-            Direction::Up | Direction::Down => self.right,
-            Direction::Left | Direction::Right => self.down,
+            Direction::Up | Direction::Down => self.right(),
+            Direction::Left | Direction::Right => self.down(),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Tile {
     Open,
     Wall,
@@ -130,17 +102,15 @@ enum Direction {
     Left,
 }
 
-#[derive(Debug)]
-struct Board {
-    tiles: Vec<Vec<Tile>>,
-    rows: usize,
-    cols: usize,
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    tiles: Grid<Option<Tile>>,
     bounds_x: Vec<Range<usize>>,
     bounds_y: Vec<Range<usize>>,
 }
 
 #[derive(Debug)]
-struct Path {
+pub struct Path {
     instructions: Vec<Instruction>,
 }
 
@@ -165,6 +135,22 @@ struct Cube {
     edges: [[Edge; Direction::LEN]; Self::FACE_LEN],
 }
 
+/// The walk along the path, one tile or turn at a time.
+#[derive(Debug)]
+struct Walk<'a> {
+    board: &'a Board,
+    /// Folds the board into a cube when set, rather than wrapping around its edges.
+    cube: Option<Cube>,
+    instructions: std::slice::Iter<'a, Instruction>,
+    /// Tiles left to move for the current instruction.
+    steps: usize,
+    pos: Pos<usize>,
+    dir: Direction,
+    face: usize,
+    /// The direction last faced on each tile walked.
+    trail: Grid<Option<Direction>>,
+}
+
 impl std::fmt::Display for Tile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -176,12 +162,12 @@ impl std::fmt::Display for Tile {
 
 impl std::fmt::Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.rows {
+        for y in 0..self.tiles.height() {
             if y > 0 {
                 f.write_char('\n')?;
             }
 
-            for x in 0..self.cols {
+            for x in 0..self.tiles.width() {
                 if let Some(tile) = self.get(Pos::new(x, y)) {
                     write!(f, "{tile}")?;
                 } else {
@@ -194,14 +180,36 @@ impl std::fmt::Display for Board {
     }
 }
 
+impl std::fmt::Display for Walk<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.board.tiles.height() {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+
+            for x in 0..self.board.tiles.width() {
+                let pos = Pos::new(x, y);
+
+                match (self.trail[pos], self.board.get(pos)) {
+                    (Some(dir), _) => f.write_char(dir.arrow())?,
+                    (None, Some(tile)) => write!(f, "{tile}")?,
+                    (None, None) => f.write_char(' ')?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for Cube {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.board.rows {
+        for y in 0..self.board.tiles.height() {
             if y > 0 {
                 f.write_char('\n')?;
             }
 
-            for x in 0..self.board.cols {
+            for x in 0..self.board.tiles.width() {
                 if self.board.get(Pos::new(x, y)).is_some() {
                     let grid = Pos::new(x / self.face_size, y / self.face_size);
                     let face_num = self
@@ -232,14 +240,14 @@ impl From<Direction> for u8 {
     }
 }
 
-impl TryFrom<u8> for Tile {
+impl TryFrom<char> for Tile {
     type Error = Error;
 
-    fn try_from(tile: u8) -> Result<Self> {
+    fn try_from(tile: char) -> Result<Self> {
         match tile {
-            b'.' => Ok(Self::Open),
-            b'#' => Ok(Self::Wall),
-            _ => bail!("invalid tile {tile}"),
+            '.' => Ok(Self::Open),
+            '#' => Ok(Self::Wall),
+            _ => bail!("invalid tile '{tile}'"),
         }
     }
 }
@@ -262,15 +270,8 @@ impl FromStr for Instruction {
     fn from_str(instruction: &str) -> Result<Self> {
         if let Ok(steps) = instruction.parse() {
             Ok(Self::Move(steps))
-        } else if let Ok(orientation) = Orientation::try_from(
-            instruction
-                .chars()
-                .next()
-                .ok_or_else(|| anyhow!("invalid instruction '{instruction}'"))?,
-        ) {
-            Ok(Self::Turn(orientation))
         } else {
-            bail!("invalid instruction '{instruction}'");
+            Ok(Self::Turn(parse::char(instruction)?))
         }
     }
 }
@@ -284,52 +285,34 @@ impl FromStr for Board {
             .lines()
             .map(str::len)
             .max()
-            .ok_or_else(|| anyhow!("invalid board"))?;
+            .filter(|&cols| cols > 0)
+            .ok_or_else(|| ParseError::new(board, "empty board"))?;
 
-        let mut tiles = vec![Vec::with_capacity(cols); rows];
+        let mut tiles = Grid::new(cols, rows, None);
         let mut bounds_x = Vec::with_capacity(rows);
 
-        for (i, row) in board.lines().map(str::as_bytes).enumerate() {
-            let mut start = 0;
-            let mut end = 0;
-
-            while end < row.len() {
-                match Tile::try_from(row[end]) {
-                    Ok(tile) => tiles[i].push(tile),
-                    Err(_) => {
-                        if tiles[i].is_empty() {
-                            start += 1;
-                        } else {
-                            break;
-                        }
-                    }
-                }
+        for (y, row) in board.lines().enumerate() {
+            let start = row.len() - row.trim_start_matches(' ').len();
+            let end = start.max(row.trim_end().len());
 
-                end += 1;
+            for (x, tile) in (start..).zip(parse::chars(&row[start..end])) {
+                tiles[Pos::new(x, y)] = Some(parse::char(tile)?);
             }
 
-            bounds_x.push(start..end);
+            // A row without tiles is empty however many spaces it has
+            bounds_x.push(if start < end { start..end } else { 0..0 });
         }
 
         let bounds_y = (0..cols)
             .map(|x| {
-                let start = bounds_x
-                    .iter()
-                    .position(|range| range.contains(&x))
-                    .unwrap();
-                let end = bounds_x
-                    .iter()
-                    .rposition(|range| range.contains(&x))
-                    .unwrap()
-                    + 1;
-                start..end
+                let start = bounds_x.iter().position(|range| range.contains(&x));
+                let end = bounds_x.iter().rposition(|range| range.contains(&x));
+                start.unwrap_or_default()..end.map_or(0, |end| end + 1)
             })
             .collect();
 
         Ok(Self {
             tiles,
-            rows,
-            cols,
             bounds_x,
             bounds_y,
         })
@@ -352,8 +335,9 @@ impl FromStr for Path {
                 }
                 instructions.push(path[start..i].parse()?);
             } else {
-                instructions.push(path[i..=i].parse()?);
-                i += 1;
+                let len = path[i..].chars().next().map_or(1, char::len_utf8);
+                instructions.push(path[i..i + len].parse()?);
+                i += len;
             }
         }
 
@@ -366,6 +350,11 @@ impl TryFrom<Board> for Cube {
 
     fn try_from(board: Board) -> Result<Self> {
         let face_size = Self::compute_face_size(&board);
+
+        if face_size == 0 || 6 * face_size * face_size != board.len() {
+            bail!("the board does not fold into a cube");
+        }
+
         let faces = Self::compute_faces(&board, face_size)?;
         let orientations = Self::compute_orientations(&faces)?;
         let edges = Self::compute_edges_from_3d(&orientations)?;
@@ -397,6 +386,16 @@ impl Direction {
         }
     }
 
+    /// How the direction is drawn on the trail.
+    const fn arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+
     const fn opposite(self) -> Self {
         match self {
             Self::Up => Self::Down,
@@ -409,13 +408,12 @@ impl Direction {
 
 impl Board {
     fn get(&self, pos: Pos<usize>) -> Option<Tile> {
-        if let Some(range) = self.bounds_x.get(pos.y())
-            && range.contains(&pos.x())
-        {
-            Some(self.tiles[pos.y()][pos.x() - range.start])
-        } else {
-            None
-        }
+        self.tiles.get(pos).copied().flatten()
+    }
+
+    /// The number of tiles on the board.
+    fn len(&self) -> usize {
+        self.tiles.iter().filter(|(_, tile)| tile.is_some()).count()
     }
 
     fn start(&self) -> Option<(Pos<usize>, Direction)> {
@@ -460,13 +458,13 @@ impl Cube {
         clippy::cast_sign_loss
     )]
     fn compute_face_size(board: &Board) -> usize {
-        let tiles = board.tiles.iter().map(Vec::len).sum::<usize>();
+        let tiles = board.len();
         ((tiles / 6) as f64).sqrt() as usize
     }
 
     fn compute_faces(board: &Board, face_size: usize) -> Result<[Face; Self::FACE_LEN]> {
-        let height = board.rows / face_size;
-        let width = board.cols / face_size;
+        let height = board.tiles.height() / face_size;
+        let width = board.tiles.width() / face_size;
 
         let mut faces = Vec::with_capacity(6);
 
@@ -474,6 +472,12 @@ impl Cube {
             for x in 0..width {
                 let origin = Pos::new(x * face_size, y * face_size);
                 if board.get(origin).is_some() {
+                    if !(0..face_size).all(|dy| {
+                        (0..face_size).all(|dx| board.get(origin + Pos::new(dx, dy)).is_some())
+                    }) {
+                        bail!("the face at {origin:?} is incomplete");
+                    }
+
                     faces.push(Face {
                         grid: Pos::new(x, y),
                         origin,
@@ -492,11 +496,7 @@ impl Cube {
         faces: &[Face; Self::FACE_LEN],
     ) -> Result<[FaceOrientation; Self::FACE_LEN]> {
         let mut orientations: [Option<FaceOrientation>; Self::FACE_LEN] = [None; Self::FACE_LEN];
-        orientations[0] = Some(FaceOrientation {
-            normal: PZ,
-            right: PX,
-            down: PY,
-        });
+        orientations[0] = Some(FaceOrientation(Rotation::IDENTITY));
 
         let mut queue = VecDeque::new();
         queue.push_back(0usize);
@@ -509,7 +509,9 @@ impl Cube {
         };
 
         while let Some(a) = queue.pop_front() {
-            let orient_a = orientations[a].unwrap();
+            let Some(orient_a) = orientations[a] else {
+                continue;
+            };
             let grid_a = faces[a].grid;
 
             for dir in [
@@ -536,15 +538,15 @@ impl Cube {
             .map(|o| o.ok_or_else(|| anyhow!("disconnected face")))
             .collect::<Result<_>>()?;
 
-        debug_assert_eq!(
-            result
-                .iter()
-                .map(|o| o.normal)
-                .collect::<std::collections::HashSet<_>>()
-                .len(),
-            6,
-            "all 6 normals must be distinct"
-        );
+        if result
+            .iter()
+            .map(|o| o.normal())
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+            != Self::FACE_LEN
+        {
+            bail!("faces overlap when folded into a cube");
+        }
 
         result
             .try_into()
@@ -564,7 +566,7 @@ impl Cube {
         let mut edge_map: HashMap<Vec3, Vec<(usize, Direction)>> = HashMap::new();
         for (f, orient) in orientations.iter().enumerate() {
             for &d in &all_dirs {
-                let edge_mid = orient.normal + orient.tangent(d);
+                let edge_mid = orient.normal() + orient.tangent(d);
                 edge_map.entry(edge_mid).or_default().push((f, d));
             }
         }
@@ -656,90 +658,148 @@ impl Cube {
     }
 }
 
-fn parse() -> Result<(Board, Path)> {
-    let input = fs::read_to_string("in/day22.txt")?;
-    let (board, path) = input
-        .split_once("\n\n")
-        .ok_or_else(|| anyhow!("invalid input"))?;
+impl<'a> Walk<'a> {
+    fn new(board: &'a Board, cube: Option<Cube>, path: &'a Path) -> Result<Self> {
+        let (pos, dir) = board
+            .start()
+            .ok_or_else(|| anyhow!("no open tile on the board"))?;
+        let face = match &cube {
+            Some(cube) => cube
+                .faces
+                .iter()
+                .position(|f| {
+                    pos.x() >= f.origin.x()
+                        && pos.x() < f.origin.x() + cube.face_size
+                        && pos.y() >= f.origin.y()
+                        && pos.y() < f.origin.y() + cube.face_size
+                })
+                .ok_or_else(|| anyhow!("the start is not on a face of the cube"))?,
+            None => 0,
+        };
+
+        let mut trail = Grid::new(board.tiles.width(), board.tiles.height(), None);
+        trail[pos] = Some(dir);
+
+        Ok(Self {
+            board,
+            cube,
+            instructions: path.instructions.iter(),
+            steps: 0,
+            pos,
+            dir,
+            face,
+            trail,
+        })
+    }
 
-    Ok((Board::from_str(board)?, Path::from_str(path)?))
+    fn password(&self) -> usize {
+        (4 * (self.pos.x() + 1)) + (1_000 * (self.pos.y() + 1)) + usize::from(u8::from(self.dir))
+    }
 }
 
-fn part1(board: &Board, path: &Path) -> usize {
-    let (mut pos, mut dir) = board.start().unwrap();
+impl Render for Board {
+    fn render(&self) -> Frame {
+        let cells = self.tiles.map(|_, &tile| match tile {
+            Some(Tile::Open) => Cell::Empty,
+            Some(Tile::Wall) => Cell::Wall,
+            None => Cell::Void,
+        });
 
-    for &instr in &path.instructions {
-        match instr {
-            Instruction::Move(steps) => {
-                for _ in 0..steps {
-                    if let Some(next) = board.step(pos, dir) {
-                        pos = next;
-                    } else {
-                        break;
-                    }
-                }
-            }
-            Instruction::Turn(turn) => dir = dir.turn(turn),
-        }
+        Frame::new(Pos::new(0, 0), cells)
     }
+}
+
+impl Render for Walk<'_> {
+    fn render(&self) -> Frame {
+        let board = self.board.render();
+        let cells = board.cells().map(|pos, &cell| {
+            if pos == self.pos {
+                Cell::Marker
+            } else if self.trail[pos].is_some() {
+                Cell::Filled
+            } else {
+                cell
+            }
+        });
 
-    (4 * (pos.x() + 1)) + (1_000 * (pos.y() + 1)) + usize::from(u8::from(dir))
+        Frame::new(board.origin(), cells)
+    }
 }
 
-fn part2(cube: &Cube, path: &Path) -> usize {
-    let (mut pos, mut dir) = cube.board.start().unwrap();
-    let mut face_idx = cube
-        .faces
-        .iter()
-        .position(|f| {
-            pos.x() >= f.origin.x()
-                && pos.x() < f.origin.x() + cube.face_size
-                && pos.y() >= f.origin.y()
-                && pos.y() < f.origin.y() + cube.face_size
-        })
-        .unwrap();
-
-    for &instr in &path.instructions {
-        match instr {
-            Instruction::Move(steps) => {
-                for _ in 0..steps {
-                    if let Some((new_pos, new_dir, new_face)) = cube.step(pos, dir, face_idx) {
-                        pos = new_pos;
-                        dir = new_dir;
-                        face_idx = new_face;
-                    } else {
-                        break;
-                    }
+impl Simulation for Walk<'_> {
+    fn step(&mut self) -> bool {
+        while self.steps == 0 {
+            match self.instructions.next() {
+                Some(&Instruction::Move(steps)) => self.steps = steps,
+                Some(&Instruction::Turn(turn)) => {
+                    self.dir = self.dir.turn(turn);
+                    self.trail[self.pos] = Some(self.dir);
+                    return true;
                 }
+                None => return false,
             }
-            Instruction::Turn(turn) => dir = dir.turn(turn),
         }
+
+        let next = match &self.cube {
+            Some(cube) => cube.step(self.pos, self.dir, self.face),
+            None => self
+                .board
+                .step(self.pos, self.dir)
+                .map(|pos| (pos, self.dir, self.face)),
+        };
+
+        if let Some((pos, dir, face)) = next {
+            (self.pos, self.dir, self.face) = (pos, dir, face);
+            self.steps -= 1;
+            self.trail[pos] = Some(dir);
+        } else {
+            // Blocked by a wall, so the rest of the move is skipped
+            self.steps = 0;
+        }
+
+        true
     }
+}
+
+fn walk(board: &Board, cube: Option<Cube>, path: &Path) -> Result<usize> {
+    let mut walk = Walk::new(board, cube, path)?;
+    while walk.step() {}
 
-    (4 * (pos.x() + 1)) + (1_000 * (pos.y() + 1)) + usize::from(u8::from(dir))
+    Ok(walk.password())
 }
 
-fn main() -> Result<()> {
-    let (board, path) = self::parse()?;
+impl Solution for Day22 {
+    type Input = (Board, Path);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (board, path) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::new(input, "missing blank line before the path"))?;
 
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&board, &path);
-        let elapsed = Instant::now().duration_since(start);
+        Ok((Board::from_str(board)?, Path::from_str(path.trim_end())?))
+    }
 
-        println!("Part 1: {part1} ({elapsed:?})");
-        // assert_eq!(part1, 73_346);
-    };
+    fn part1((board, path): &Self::Input) -> Result<impl std::fmt::Display> {
+        self::walk(board, None, path)
+    }
 
-    {
-        let cube = Cube::try_from(board)?;
-        let start = Instant::now();
-        let part2 = self::part2(&cube, &path);
-        let elapsed = Instant::now().duration_since(start);
+    fn part2((board, path): &Self::Input) -> Result<impl std::fmt::Display> {
+        let cube = Cube::try_from(board.clone())?;
+        self::walk(board, Some(cube), path)
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        // assert_eq!(part2, 0);
-    };
+    fn simulate(
+        (board, path): &Self::Input,
+        part: Part,
+    ) -> Option<Result<Box<dyn Simulation + '_>>> {
+        let cube = match part {
+            Part::One => None,
+            Part::Two => match Cube::try_from(board.clone()) {
+                Ok(cube) => Some(cube),
+                Err(err) => return Some(Err(err)),
+            },
+        };
 
-    Ok(())
+        Some(Walk::new(board, cube, path).map(|walk| Box::new(walk) as Box<dyn Simulation>))
+    }
 }
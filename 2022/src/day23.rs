@@ -1,13 +1,20 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::bail;
-use aoc_2022::Pos;
+use aoc_common::Grid;
+use aoc_common::Part;
+use aoc_common::Pos;
+use aoc_common::Solution;
+use aoc_common::parse::ParseError;
+use aoc_common::render::Cell;
+use aoc_common::render::Frame;
+use aoc_common::render::Render;
+use aoc_common::visualize::Simulation;
+
+pub struct Day23;
 
 #[derive(Debug, Clone, Copy)]
 enum Dir {
@@ -18,11 +25,19 @@ enum Dir {
 }
 
 #[derive(Debug, Clone)]
-struct Grove {
+pub struct Grove {
     elves: HashSet<Pos<i64>>,
     dir_idx: usize,
 }
 
+/// The elves spreading out over the grove, one round at a time.
+#[derive(Debug)]
+struct Spread {
+    grove: Grove,
+    /// Rounds left to play, or `None` to play until the elves stop moving.
+    rounds: Option<usize>,
+}
+
 impl std::fmt::Display for Grove {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let x_min = self.x_min() - 1;
@@ -48,6 +63,30 @@ impl std::fmt::Display for Grove {
     }
 }
 
+/// Groves are equal if their elves are spread out the same way, wherever that is, as the grove
+/// extends forever.
+impl PartialEq for Grove {
+    fn eq(&self, other: &Self) -> bool {
+        let origin = |grove: &Self| Pos::new(grove.x_min(), grove.y_min());
+        let offset = origin(other) - origin(self);
+
+        self.dir_idx == other.dir_idx
+            && self.elves.len() == other.elves.len()
+            && self
+                .elves
+                .iter()
+                .all(|&elf| other.elves.contains(&(elf + offset)))
+    }
+}
+
+impl Eq for Grove {}
+
+impl std::fmt::Display for Spread {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grove)
+    }
+}
+
 impl FromStr for Grove {
     type Err = Error;
 
@@ -65,7 +104,7 @@ impl FromStr for Grove {
         }
 
         if elves.is_empty() {
-            bail!("no elves in the grove!");
+            return Err(ParseError::new(grid, "no elves in the grove").into());
         }
 
         Ok(Self { elves, dir_idx: 0 })
@@ -81,10 +120,10 @@ impl Dir {
 
     fn adj(self, pos: Pos<i64>) -> impl Iterator<Item = Pos<i64>> {
         match self {
-            Self::North => [pos.up(), pos.up().right(), pos.up().left()],
-            Self::East => [pos.right(), pos.right().up(), pos.right().down()],
-            Self::South => [pos.down(), pos.down().right(), pos.down().left()],
-            Self::West => [pos.left(), pos.left().up(), pos.left().down()],
+            Self::North => [pos.up(), pos.up_right(), pos.up_left()],
+            Self::East => [pos.right(), pos.up_right(), pos.down_right()],
+            Self::South => [pos.down(), pos.down_right(), pos.down_left()],
+            Self::West => [pos.left(), pos.up_left(), pos.down_left()],
         }
         .into_iter()
     }
@@ -100,6 +139,9 @@ impl Dir {
 }
 
 impl Grove {
+    /// The rounds played in part 1.
+    const ROUNDS: usize = 10;
+
     fn x_min(&self) -> i64 {
         self.elves
             .iter()
@@ -146,10 +188,7 @@ impl Grove {
         let mut moved = false;
 
         for &elf in &self.elves {
-            if !self
-                .dirs()
-                .any(|dir| dir.adj(elf).any(|adj| self.elves.contains(&adj)))
-            {
+            if !elf.adj8().any(|adj| self.elves.contains(&adj)) {
                 // No neighbors
                 continue;
             }
@@ -178,10 +217,50 @@ impl Grove {
     }
 }
 
-fn part1(grove: &mut Grove) -> u64 {
-    const ROUNDS: usize = 10;
+/// The elves with a margin of empty ground around them, as drawn by `Display`.
+impl Render for Grove {
+    fn render(&self) -> Frame {
+        let min = Pos::new(self.x_min() - 1, self.y_min() - 1);
+        let max = Pos::new(self.x_max() + 1, self.y_max() + 1);
+        let width = max.x().abs_diff(min.x()) + 1;
+        let height = max.y().abs_diff(min.y()) + 1;
+
+        let mut frame = Frame::new(
+            min,
+            Grid::new(
+                usize::try_from(width).unwrap_or(0),
+                usize::try_from(height).unwrap_or(0),
+                Cell::Empty,
+            ),
+        );
+
+        for &elf in &self.elves {
+            frame.set(elf, Cell::Active);
+        }
+
+        frame
+    }
+}
+
+impl Render for Spread {
+    fn render(&self) -> Frame {
+        self.grove.render()
+    }
+}
+
+impl Simulation for Spread {
+    fn step(&mut self) -> bool {
+        if self.rounds == Some(0) {
+            return false;
+        }
 
-    for _ in 0..ROUNDS {
+        self.rounds = self.rounds.map(|rounds| rounds - 1);
+        self.grove.simulate_round()
+    }
+}
+
+fn part1(grove: &mut Grove) -> u64 {
+    for _ in 0..Grove::ROUNDS {
         grove.simulate_round();
     }
 
@@ -199,27 +278,25 @@ fn part2(grove: &mut Grove) -> usize {
     round
 }
 
-fn main() -> Result<()> {
-    let mut grove = Grove::from_str(&fs::read_to_string("in/day23.txt")?)?;
-
-    {
-        let mut grove = grove.clone();
-        let start = Instant::now();
-        let part1 = self::part1(&mut grove);
-        let elapsed = Instant::now().duration_since(start);
+impl Solution for Day23 {
+    type Input = Grove;
 
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 3_925);
-    };
+    fn parse(input: &str) -> Result<Self::Input> {
+        Grove::from_str(input)
+    }
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&mut grove);
-        let elapsed = Instant::now().duration_since(start);
+    fn part1(grove: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(&mut grove.clone()))
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 903);
-    };
+    fn part2(grove: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(&mut grove.clone()))
+    }
 
-    Ok(())
+    fn simulate(grove: &Self::Input, part: Part) -> Option<Result<Box<dyn Simulation + '_>>> {
+        Some(Ok(Box::new(Spread {
+            grove: grove.clone(),
+            rounds: (part == Part::One).then_some(Grove::ROUNDS),
+        })))
+    }
 }
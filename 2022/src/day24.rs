@@ -0,0 +1,375 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use aoc_common::Grid;
+use aoc_common::Part;
+use aoc_common::Pos;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+use aoc_common::render::Cell;
+use aoc_common::render::Frame;
+use aoc_common::render::Render;
+use aoc_common::search;
+use aoc_common::visualize::Simulation;
+
+pub struct Day24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Tile {
+    Wall,
+    Ground,
+    Blizzard(Dir),
+    Blizzards(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Valley {
+    walls: Grid<bool>,
+    blizzards: Vec<(Pos<usize>, Dir)>,
+}
+
+/// The expedition crossing the valley, tracking every position it could be in after each minute.
+#[derive(Debug)]
+struct Expedition {
+    valley: Valley,
+    reachable: HashSet<Pos<usize>>,
+    /// The positions still to reach, the next one last.
+    trips: Vec<Pos<usize>>,
+}
+
+impl std::fmt::Display for Dir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Up => write!(f, "^"),
+            Self::Down => write!(f, "v"),
+            Self::Left => write!(f, "<"),
+            Self::Right => write!(f, ">"),
+        }
+    }
+}
+
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wall => write!(f, "#"),
+            Self::Ground => write!(f, "."),
+            &Self::Blizzard(dir) => write!(f, "{dir}"),
+            &Self::Blizzards(count) => write!(f, "{count}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Valley {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.draw(f, &HashSet::new())
+    }
+}
+
+impl std::fmt::Display for Expedition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.valley.draw(f, &self.reachable)
+    }
+}
+
+impl TryFrom<char> for Dir {
+    type Error = Error;
+
+    fn try_from(dir: char) -> Result<Self> {
+        match dir {
+            '^' => Ok(Self::Up),
+            'v' => Ok(Self::Down),
+            '<' => Ok(Self::Left),
+            '>' => Ok(Self::Right),
+            _ => bail!("invalid dir '{dir}'"),
+        }
+    }
+}
+
+impl FromStr for Valley {
+    type Err = Error;
+
+    fn from_str(valley: &str) -> Result<Self> {
+        let mut blizzards = Vec::new();
+        let walls = Grid::parse(valley, |pos, tile| {
+            if tile != "#" && tile != "." {
+                blizzards.push((pos, tile));
+            }
+
+            Ok(tile == "#")
+        })?;
+
+        let (width, height) = (walls.width(), walls.height());
+
+        if width < 3 || height < 3 {
+            return Err(ParseError::new(valley, "valley is too small").into());
+        }
+
+        let blizzards = blizzards
+            .into_iter()
+            .map(|(pos, tile)| {
+                if (1..width - 1).contains(&pos.x()) && (1..height - 1).contains(&pos.y()) {
+                    Ok((pos, parse::char(tile)?))
+                } else {
+                    Err(ParseError::new(tile, "invalid tile").into())
+                }
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { walls, blizzards })
+    }
+}
+
+impl Valley {
+    const START: Pos<usize> = Pos::new(1, 0);
+
+    fn tile(&self, pos: Pos<usize>) -> Option<Tile> {
+        let mut blizzards = self
+            .blizzards
+            .iter()
+            .filter(|(blizzard, _)| *blizzard == pos);
+
+        if let Some(&(_, dir)) = blizzards.next() {
+            Some(match blizzards.count() {
+                0 => Tile::Blizzard(dir),
+                n => Tile::Blizzards(n + 1),
+            })
+        } else {
+            self.walls
+                .get(pos)
+                .map(|&wall| if wall { Tile::Wall } else { Tile::Ground })
+        }
+    }
+
+    /// Draws the valley, with the expedition as `E` wherever it could be.
+    fn draw(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        expedition: &HashSet<Pos<usize>>,
+    ) -> std::fmt::Result {
+        for y in 0..self.walls.height() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for x in 0..self.walls.width() {
+                let pos = Pos::new(x, y);
+
+                if expedition.contains(&pos) {
+                    write!(f, "E")?;
+                } else {
+                    write!(f, "{}", self.tile(pos).ok_or(std::fmt::Error)?)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the valley, with the expedition wherever it could be.
+    fn frame(&self, expedition: &HashSet<Pos<usize>>) -> Frame {
+        let blizzards = self
+            .blizzards
+            .iter()
+            .map(|&(pos, _)| pos)
+            .collect::<HashSet<_>>();
+
+        let cells = self.walls.map(|pos, &wall| {
+            if expedition.contains(&pos) {
+                Cell::Marker
+            } else if wall {
+                Cell::Wall
+            } else if blizzards.contains(&pos) {
+                Cell::Active
+            } else {
+                Cell::Empty
+            }
+        });
+
+        Frame::new(Pos::new(0, 0), cells)
+    }
+
+    const fn goal(&self) -> Pos<usize> {
+        Pos::new(self.walls.width() - 2, self.walls.height() - 1)
+    }
+
+    fn is_open(&self, pos: Pos<usize>) -> bool {
+        self.walls.get(pos).is_some_and(|&wall| !wall)
+    }
+
+    fn update_blizzards(&mut self) {
+        for (pos, dir) in &mut self.blizzards {
+            *pos = match dir {
+                Dir::Up if pos.y() == 1 => Pos::new(pos.x(), self.walls.height() - 2),
+                Dir::Down if pos.y() == self.walls.height() - 2 => Pos::new(pos.x(), 1),
+                Dir::Left if pos.x() == 1 => Pos::new(self.walls.width() - 2, pos.y()),
+                Dir::Right if pos.x() == self.walls.width() - 2 => Pos::new(1, pos.y()),
+                Dir::Up => Pos::new(pos.x(), pos.y() - 1),
+                Dir::Down => Pos::new(pos.x(), pos.y() + 1),
+                Dir::Left => Pos::new(pos.x() - 1, pos.y()),
+                Dir::Right => Pos::new(pos.x() + 1, pos.y()),
+            };
+        }
+    }
+
+    fn blizzard_snapshots(&mut self) -> Vec<HashSet<Pos<usize>>> {
+        const fn lcm(a: usize, b: usize) -> usize {
+            const fn gcd(a: usize, b: usize) -> usize {
+                if b == 0 { a } else { gcd(b, a % b) }
+            }
+
+            a / gcd(a, b) * b
+        }
+
+        let period = lcm(self.walls.width() - 2, self.walls.height() - 2);
+        let mut snapshots = Vec::with_capacity(period);
+
+        for _ in 0..period {
+            snapshots.push(self.blizzards.iter().map(|(p, _)| *p).collect());
+            self.update_blizzards();
+        }
+
+        snapshots
+    }
+
+    /// The time of arrival at `goal` when leaving `start` at `start_time`, waiting out or
+    /// dodging the blizzards on the way.
+    fn arrival(
+        &self,
+        snapshots: &[HashSet<Pos<usize>>],
+        start: Pos<usize>,
+        goal: Pos<usize>,
+        start_time: usize,
+    ) -> Result<usize> {
+        let period = snapshots.len();
+
+        // The blizzards repeat, so states only differ by the time within the period
+        let successors = |&(pos, time): &(Pos<usize>, usize)| {
+            let time = (time + 1) % period;
+            let snap = &snapshots[time];
+
+            [Some(pos), pos.up(), pos.down(), pos.left(), pos.right()]
+                .into_iter()
+                .flatten()
+                .filter(move |&next| self.is_open(next) && !snap.contains(&next))
+                .map(move |next| (next, time))
+        };
+
+        search::bfs((start, start_time % period), successors, |&(pos, _)| {
+            pos == goal
+        })
+        .map(|path| start_time + path.cost())
+        .ok_or_else(|| {
+            anyhow!(
+                "no path from ({}, {}) to ({}, {})",
+                start.x(),
+                start.y(),
+                goal.x(),
+                goal.y()
+            )
+        })
+    }
+}
+
+impl Render for Valley {
+    fn render(&self) -> Frame {
+        self.frame(&HashSet::new())
+    }
+}
+
+impl Render for Expedition {
+    fn render(&self) -> Frame {
+        self.valley.frame(&self.reachable)
+    }
+}
+
+impl Simulation for Expedition {
+    fn step(&mut self) -> bool {
+        let Some(&goal) = self.trips.last() else {
+            return false;
+        };
+
+        if self.reachable.is_empty() {
+            // Caught by the blizzards wherever it went
+            return false;
+        }
+
+        self.valley.update_blizzards();
+
+        let blizzards = self
+            .valley
+            .blizzards
+            .iter()
+            .map(|&(pos, _)| pos)
+            .collect::<HashSet<_>>();
+
+        self.reachable = self
+            .reachable
+            .iter()
+            .flat_map(|&pos| [Some(pos), pos.up(), pos.down(), pos.left(), pos.right()])
+            .flatten()
+            .filter(|&pos| self.valley.is_open(pos) && !blizzards.contains(&pos))
+            .collect();
+
+        if self.reachable.contains(&goal) {
+            self.trips.pop();
+            self.reachable = HashSet::from([goal]);
+        }
+
+        true
+    }
+}
+
+fn part1(valley: &Valley, snapshots: &[HashSet<Pos<usize>>]) -> Result<usize> {
+    valley.arrival(snapshots, Valley::START, valley.goal(), 0)
+}
+
+fn part2(valley: &Valley, snapshots: &[HashSet<Pos<usize>>]) -> Result<usize> {
+    let t1 = valley.arrival(snapshots, Valley::START, valley.goal(), 0)?;
+    let t2 = valley.arrival(snapshots, valley.goal(), Valley::START, t1)?;
+    valley.arrival(snapshots, Valley::START, valley.goal(), t2)
+}
+
+impl Solution for Day24 {
+    type Input = (Valley, Vec<HashSet<Pos<usize>>>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut valley = Valley::from_str(input)?;
+        let snapshots = valley.blizzard_snapshots();
+
+        Ok((valley, snapshots))
+    }
+
+    fn part1((valley, snapshots): &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part1(valley, snapshots)
+    }
+
+    fn part2((valley, snapshots): &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part2(valley, snapshots)
+    }
+
+    fn simulate((valley, _): &Self::Input, part: Part) -> Option<Result<Box<dyn Simulation + '_>>> {
+        let trips = match part {
+            Part::One => vec![valley.goal()],
+            Part::Two => vec![valley.goal(), Valley::START, valley.goal()],
+        };
+
+        Some(Ok(Box::new(Expedition {
+            valley: valley.clone(),
+            reachable: HashSet::from([Valley::START]),
+            trips,
+        })))
+    }
+}
@@ -1,13 +1,16 @@
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::bail;
+use anyhow::anyhow;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day25;
 
 #[derive(Debug)]
-struct Snafu {
+pub struct Snafu {
     snafu: String,
     decimal: i64,
 }
@@ -16,21 +19,23 @@ impl FromStr for Snafu {
     type Err = Error;
 
     fn from_str(snafu: &str) -> Result<Self> {
-        let mut decimal = 0;
-        let mut power = 1;
+        let mut decimal = 0_i64;
+        let mut power = Some(1_i64);
 
-        for digit in snafu.chars().rev() {
+        for digit in parse::chars(snafu).rev() {
             let val = match digit {
-                '2' => 2,
-                '1' => 1,
-                '0' => 0,
-                '-' => -1,
-                '=' => -2,
-                _ => bail!("invalid SNAFU digit '{digit}'"),
+                "2" => 2_i64,
+                "1" => 1,
+                "0" => 0,
+                "-" => -1,
+                "=" => -2,
+                _ => return Err(ParseError::new(digit, "invalid SNAFU digit").into()),
             };
 
-            decimal += val * power;
-            power *= 5;
+            decimal = power
+                .and_then(|power| decimal.checked_add(val.checked_mul(power)?))
+                .ok_or_else(|| ParseError::new(snafu, "SNAFU number is too large"))?;
+            power = power.and_then(|power| power.checked_mul(5));
         }
 
         Ok(Self {
@@ -73,28 +78,30 @@ impl From<i64> for Snafu {
     }
 }
 
-fn part1(fuel: &[Snafu]) -> String {
-    Snafu::from(fuel.iter().map(|snafu| snafu.decimal).sum::<i64>()).snafu
-}
+fn part1(fuel: &[Snafu]) -> Result<String> {
+    let sum = fuel
+        .iter()
+        .try_fold(0_i64, |sum, snafu| sum.checked_add(snafu.decimal))
+        .ok_or_else(|| anyhow!("the fuel requirements add up to more than {}", i64::MAX))?;
 
-fn main() -> Result<()> {
-    let fuel = fs::read_to_string("in/day25.txt")?
-        .lines()
-        .map(Snafu::from_str)
-        .collect::<Result<Vec<_>>>()?;
+    Ok(Snafu::from(sum).snafu)
+}
 
-    dbg!(&fuel);
+impl Solution for Day25 {
+    type Input = Vec<Snafu>;
 
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&fuel);
-        let elapsed = start.elapsed();
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(Snafu::from_str).collect()
+    }
 
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, "2-2=12=1-=-1=000=222");
-    };
+    fn part1(fuel: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part1(fuel)
+    }
 
-    Ok(())
+    /// Day 25 has no second puzzle: its star is awarded for collecting the other 49.
+    fn part2(_: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok("Merry Christmas!")
+    }
 }
 
 #[cfg(test)]
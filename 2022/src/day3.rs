@@ -1,14 +1,17 @@
 use std::collections::HashSet;
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
 use anyhow::bail;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day3;
 
 #[derive(Debug)]
-struct Rucksack {
+pub struct Rucksack {
     items: Vec<char>,
 }
 
@@ -20,8 +23,10 @@ impl FromStr for Rucksack {
     type Err = Error;
 
     fn from_str(rucksack: &str) -> Result<Self> {
-        if rucksack.chars().any(|item| !item.is_alphabetic()) {
-            bail!("invalid rucksack '{rucksack}'");
+        if let Some(item) =
+            parse::chars(rucksack).find(|item| !item.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            return Err(ParseError::new(item, "invalid item").into());
         }
 
         Ok(Self {
@@ -58,14 +63,21 @@ fn part1(rucksacks: &[Rucksack]) -> u64 {
         .sum()
 }
 
-fn part2(rucksacks: &[Rucksack]) -> u64 {
+fn part2(rucksacks: &[Rucksack]) -> Result<u64> {
     let mut sum = 0;
 
     for group in rucksacks.chunks(3) {
-        let g1 = group[0].items.iter().copied().collect::<HashSet<_>>();
-        let g2 = group[1].items.iter().copied().collect::<HashSet<_>>();
+        let [r1, r2, r3] = group else {
+            bail!(
+                "{} rucksacks cannot be split into groups of 3",
+                rucksacks.len()
+            );
+        };
+
+        let g1 = r1.items.iter().copied().collect::<HashSet<_>>();
+        let g2 = r2.items.iter().copied().collect::<HashSet<_>>();
 
-        sum += group[2]
+        sum += r3
             .items
             .iter()
             .copied()
@@ -75,32 +87,21 @@ fn part2(rucksacks: &[Rucksack]) -> u64 {
             .unwrap_or_default();
     }
 
-    sum
+    Ok(sum)
 }
 
-fn main() -> Result<()> {
-    let rucksacks = fs::read_to_string("in/day3.txt")?
-        .lines()
-        .map(Rucksack::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&rucksacks);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 8_515);
-    };
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&rucksacks);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(Rucksack::from_str).collect()
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 2_434);
-    };
+    fn part1(rucksacks: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(rucksacks))
+    }
 
-    Ok(())
+    fn part2(rucksacks: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part2(rucksacks)
+    }
 }
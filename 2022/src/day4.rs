@@ -1,10 +1,12 @@
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::anyhow;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day4;
 
 #[derive(Debug)]
 struct Range {
@@ -13,7 +15,7 @@ struct Range {
 }
 
 #[derive(Debug)]
-struct Section {
+pub struct Section {
     pair: (Range, Range),
 }
 
@@ -23,11 +25,11 @@ impl FromStr for Range {
     fn from_str(range: &str) -> Result<Self> {
         let (min, max) = range
             .split_once('-')
-            .ok_or_else(|| anyhow!("invalid range '{range}'"))?;
+            .ok_or_else(|| ParseError::new(range, "invalid range"))?;
 
         Ok(Self {
-            min: min.parse()?,
-            max: max.parse()?,
+            min: parse::from_str(min)?,
+            max: parse::from_str(max)?,
         })
     }
 }
@@ -38,7 +40,7 @@ impl FromStr for Section {
     fn from_str(section: &str) -> Result<Self> {
         let (first, second) = section
             .split_once(',')
-            .ok_or_else(|| anyhow!("invalid section '{section}'"))?;
+            .ok_or_else(|| ParseError::new(section, "invalid section"))?;
 
         Ok(Self {
             pair: (first.parse()?, second.parse()?),
@@ -82,29 +84,18 @@ fn part2(sections: &[Section]) -> usize {
         .count()
 }
 
-fn main() -> Result<()> {
-    let sections = fs::read_to_string("in/day4.txt")?
-        .lines()
-        .map(Section::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&sections);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 450);
-    };
+impl Solution for Day4 {
+    type Input = Vec<Section>;
 
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&sections);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(Section::from_str).collect()
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 837);
-    };
+    fn part1(sections: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(sections))
+    }
 
-    Ok(())
+    fn part2(sections: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(sections))
+    }
 }
@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day5;
+
+#[derive(Clone, Debug)]
+struct Instruction {
+    quantity: usize,
+    from: usize,
+    to: usize,
+}
+
+trait CrateMover {
+    fn move_crates(stacks: &mut [Vec<char>], instruction: &Instruction) -> Result<()>;
+}
+
+struct CrateMover9000;
+struct CrateMover9001;
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    stacks: Vec<Vec<char>>,
+    instructions: Vec<Instruction>,
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(instruction: &str) -> Result<Self> {
+        let parts = instruction.split_ascii_whitespace().collect::<Vec<_>>();
+
+        if parts.len() != 6 {
+            return Err(ParseError::new(instruction, "invalid instruction").into());
+        }
+
+        let stack = |stack| {
+            parse::from_str::<usize>(stack)?
+                .checked_sub(1)
+                .ok_or_else(|| ParseError::new(stack, "stacks are numbered from 1"))
+        };
+
+        Ok(Self {
+            quantity: parse::from_str(parts[1])?,
+            from: stack(parts[3])?,
+            to: stack(parts[5])?,
+        })
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (crates, instructions) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::new(input, "missing blank line before the instructions"))?;
+
+        let mut rows = crates.lines().rev();
+        let len = rows
+            .next()
+            .ok_or_else(|| ParseError::new(crates, "missing stacks"))?
+            .split_ascii_whitespace()
+            .count();
+
+        let mut stacks = vec![vec![]; len];
+        for line in rows {
+            for (i, chunk) in line.as_bytes().chunks(4).enumerate() {
+                if chunk[0] == b'[' {
+                    let (Some(&c), Some(stack)) = (chunk.get(1), stacks.get_mut(i)) else {
+                        let snippet = line.get(4 * i..).unwrap_or(line);
+
+                        return Err(ParseError::new(snippet, "invalid crate").into());
+                    };
+
+                    stack.push(char::from(c));
+                }
+            }
+        }
+
+        let instructions = instructions
+            .lines()
+            .map(|line| {
+                let instruction = Instruction::from_str(line)?;
+
+                if instruction.from >= len || instruction.to >= len {
+                    return Err(
+                        ParseError::new(line, format!("there are only {len} stacks")).into(),
+                    );
+                }
+
+                Ok(instruction)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            stacks,
+            instructions,
+        })
+    }
+}
+
+impl Instruction {
+    /// The error for moving more crates than the stack moved from holds.
+    fn too_many(&self, held: usize) -> Error {
+        anyhow!(
+            "cannot move {} crates from stack {} holding {held}",
+            self.quantity,
+            self.from + 1
+        )
+    }
+}
+
+impl CrateMover for CrateMover9000 {
+    fn move_crates(stacks: &mut [Vec<char>], instruction: &Instruction) -> Result<()> {
+        // Checked up front, as a stack moved onto itself never runs out
+        let held = stacks[instruction.from].len();
+
+        if instruction.quantity > held {
+            return Err(instruction.too_many(held));
+        }
+
+        for _ in 0..instruction.quantity {
+            let c = stacks[instruction.from]
+                .pop()
+                .ok_or_else(|| instruction.too_many(held))?;
+            stacks[instruction.to].push(c);
+        }
+
+        Ok(())
+    }
+}
+
+impl CrateMover for CrateMover9001 {
+    fn move_crates(stacks: &mut [Vec<char>], instruction: &Instruction) -> Result<()> {
+        let from = &mut stacks[instruction.from];
+        let at = from
+            .len()
+            .checked_sub(instruction.quantity)
+            .ok_or_else(|| instruction.too_many(from.len()))?;
+        let crates = from.split_off(at);
+        stacks[instruction.to].extend(crates);
+
+        Ok(())
+    }
+}
+
+impl Puzzle {
+    fn execute<M: CrateMover>(mut self) -> Result<String> {
+        for instruction in &self.instructions {
+            M::move_crates(&mut self.stacks, instruction)?;
+        }
+
+        Ok(self
+            .stacks
+            .iter()
+            .filter_map(|stack| stack.last())
+            .collect())
+    }
+}
+
+impl Solution for Day5 {
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Puzzle::from_str(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Result<impl std::fmt::Display> {
+        puzzle.clone().execute::<CrateMover9000>()
+    }
+
+    fn part2(puzzle: &Self::Input) -> Result<impl std::fmt::Display> {
+        puzzle.clone().execute::<CrateMover9001>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_many_crates() {
+        let puzzle = Day5::parse("    [D]\n[N] [C]\n 1   2\n\nmove 3 from 1 to 2\n").unwrap();
+
+        for result in [
+            puzzle.clone().execute::<CrateMover9000>(),
+            puzzle.execute::<CrateMover9001>(),
+        ] {
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "cannot move 3 crates from stack 1 holding 1"
+            );
+        }
+
+        // Even onto the same stack, which would never run out
+        let puzzle = Day5::parse("    [D]\n[N] [C]\n 1   2\n\nmove 3 from 1 to 1\n").unwrap();
+
+        for result in [
+            puzzle.clone().execute::<CrateMover9000>(),
+            puzzle.execute::<CrateMover9001>(),
+        ] {
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "cannot move 3 crates from stack 1 holding 1"
+            );
+        }
+    }
+}
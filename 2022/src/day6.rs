@@ -0,0 +1,39 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use aoc_common::Solution;
+
+pub struct Day6;
+
+#[derive(Debug)]
+pub struct Signal {
+    buffer: String,
+}
+
+impl Signal {
+    fn find_unique_sequence(&self, len: usize) -> Option<usize> {
+        self.buffer
+            .as_bytes()
+            .windows(len)
+            .position(|w| w.iter().copied().collect::<HashSet<_>>().len() == len)
+            .map(|i| i + len)
+    }
+}
+
+impl Solution for Day6 {
+    type Input = Signal;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Signal {
+            buffer: input.to_string(),
+        })
+    }
+
+    fn part1(signal: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(signal.find_unique_sequence(4).unwrap_or_default())
+    }
+
+    fn part2(signal: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(signal.find_unique_sequence(14).unwrap_or_default())
+    }
+}
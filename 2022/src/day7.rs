@@ -0,0 +1,306 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
+
+pub struct Day7;
+
+#[derive(Debug)]
+enum FileKind {
+    Dir { children: Vec<usize> },
+    Regular { size: usize },
+}
+
+#[derive(Debug)]
+struct File {
+    name: String,
+    kind: FileKind,
+    parent: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct FileSystem {
+    files: Vec<File>,
+}
+
+/// Writes the terminal output of listing every directory, depth first, which parses back into
+/// the same file system.
+impl std::fmt::Display for FileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // A directory to list, or `None` to go back up once one has been
+        let mut stack = vec![Some(0)];
+
+        while let Some(dir) = stack.pop() {
+            let Some(dir) = dir else {
+                writeln!(f, "$ cd ..")?;
+                continue;
+            };
+            let FileKind::Dir { children } = &self.files[dir].kind else {
+                continue;
+            };
+
+            writeln!(f, "$ cd {}", self.files[dir].name)?;
+            writeln!(f, "$ ls")?;
+
+            for &child in children {
+                let file = &self.files[child];
+
+                match file.kind {
+                    FileKind::Dir { .. } => writeln!(f, "dir {}", file.name)?,
+                    FileKind::Regular { size } => writeln!(f, "{size} {}", file.name)?,
+                }
+            }
+
+            for &child in children.iter().rev() {
+                if self.files[child].is_dir() {
+                    stack.extend([None, Some(child)]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// File systems are equal if they hold the same files, in whatever order they were found.
+impl PartialEq for FileSystem {
+    fn eq(&self, other: &Self) -> bool {
+        let mut pairs = vec![(0, 0)];
+
+        while let Some((a, b)) = pairs.pop() {
+            let (a, b) = (&self.files[a], &other.files[b]);
+
+            if a.name != b.name {
+                return false;
+            }
+
+            match (&a.kind, &b.kind) {
+                (FileKind::Dir { children: a }, FileKind::Dir { children: b }) => {
+                    if a.len() != b.len() {
+                        return false;
+                    }
+
+                    // Names are unique within a directory
+                    let sorted = |fs: &'_ Self, children: &[usize]| {
+                        let mut children = children.to_vec();
+                        children.sort_by(|&a, &b| fs.files[a].name.cmp(&fs.files[b].name));
+                        children
+                    };
+
+                    pairs.extend(sorted(self, a).into_iter().zip(sorted(other, b)));
+                }
+                (FileKind::Regular { size: a }, FileKind::Regular { size: b }) if a == b => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl Eq for FileSystem {}
+
+impl FromStr for FileSystem {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut fs = Self::new();
+        let mut cwd = 0;
+
+        for block in input
+            .split('$')
+            .map(str::trim)
+            .filter(|&block| !block.is_empty())
+        {
+            if let Some(dir) = block.strip_prefix("cd ") {
+                cwd = match dir {
+                    "/" => 0,
+                    ".." => fs.files[cwd]
+                        .parent
+                        .ok_or_else(|| ParseError::new(dir, "already at the root directory"))?,
+                    name => match fs.child(cwd, self::name(name)?) {
+                        Some(child) if !fs.files[child].is_dir() => {
+                            return Err(ParseError::new(dir, "cd into non-directory").into());
+                        }
+                        Some(child) => child,
+                        None => fs.add(name.to_string(), FileKind::Dir { children: vec![] }, cwd),
+                    },
+                };
+            } else if block.starts_with("ls") {
+                for line in block.lines().skip(1) {
+                    if let Some(name) = line.strip_prefix("dir ") {
+                        fs.list(name, FileKind::Dir { children: vec![] }, cwd)?;
+                    } else {
+                        let (size, name) = line
+                            .split_once(' ')
+                            .ok_or_else(|| ParseError::new(line, "invalid file"))?;
+                        let size = parse::from_str(size)?;
+
+                        fs.list(name, FileKind::Regular { size }, cwd)?;
+                    }
+                }
+            } else {
+                let command = block.lines().next().unwrap_or_default();
+
+                return Err(ParseError::new(command, "unknown command").into());
+            }
+        }
+
+        Ok(fs)
+    }
+}
+
+impl File {
+    const fn is_dir(&self) -> bool {
+        matches!(self.kind, FileKind::Dir { .. })
+    }
+}
+
+impl FileSystem {
+    fn new() -> Self {
+        Self {
+            files: vec![File {
+                name: "/".to_string(),
+                kind: FileKind::Dir { children: vec![] },
+                parent: None,
+            }],
+        }
+    }
+
+    /// The file called `name` in the directory `dir`, if there is one.
+    fn child(&self, dir: usize, name: &str) -> Option<usize> {
+        match &self.files[dir].kind {
+            FileKind::Dir { children } => children
+                .iter()
+                .copied()
+                .find(|&child| self.files[child].name == name),
+            FileKind::Regular { .. } => None,
+        }
+    }
+
+    /// Adds a file listed in the directory `dir`, unless it was listed there before, in which
+    /// case it must be listed the same way.
+    fn list(&mut self, name: &str, kind: FileKind, dir: usize) -> Result<()> {
+        match self.child(dir, self::name(name)?) {
+            None => {
+                self.add(name.to_string(), kind, dir);
+            }
+            Some(file) => match (&self.files[file].kind, &kind) {
+                (FileKind::Dir { .. }, FileKind::Dir { .. }) => {}
+                (FileKind::Regular { size }, FileKind::Regular { size: listed })
+                    if size == listed => {}
+                _ => return Err(ParseError::new(name, "listed differently before").into()),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn add(&mut self, name: String, kind: FileKind, parent: usize) -> usize {
+        let idx = self.files.len();
+
+        self.files.push(File {
+            name,
+            kind,
+            parent: Some(parent),
+        });
+
+        if let FileKind::Dir { children } = &mut self.files[parent].kind {
+            children.push(idx);
+        }
+
+        idx
+    }
+
+    /// Sizes of the directories, starting with the root.
+    fn dir_sizes(&self) -> Result<Vec<usize>> {
+        let mut sizes = vec![0_usize; self.files.len()];
+
+        // Files are always added after their parent, so totals can be accumulated bottom-up
+        // without recursing through arbitrarily deep directories
+        for (i, file) in self.files.iter().enumerate().rev() {
+            if let FileKind::Regular { size } = file.kind {
+                sizes[i] = size;
+            }
+
+            if let Some(parent) = file.parent {
+                sizes[parent] = sizes[parent].checked_add(sizes[i]).ok_or_else(|| {
+                    anyhow!("directory '{}' is too large", self.files[parent].name)
+                })?;
+            }
+        }
+
+        Ok(sizes
+            .into_iter()
+            .zip(&self.files)
+            .filter(|(_, file)| file.is_dir())
+            .map(|(size, _)| size)
+            .collect())
+    }
+}
+
+/// Checks that `name` can be written back as it was read: not empty, `/` or `..`, which `cd`
+/// treats specially, and without whitespace or `$`, which separate commands and their output.
+fn name(name: &str) -> Result<&str> {
+    if name.is_empty()
+        || name == ".."
+        || name.contains(|c: char| c.is_whitespace() || c == '$' || c == '/')
+    {
+        return Err(ParseError::new(name, "invalid file name").into());
+    }
+
+    Ok(name)
+}
+
+fn part1(fs: &FileSystem) -> Result<usize> {
+    const SIZE_MAX: usize = 100_000;
+
+    Ok(fs
+        .dir_sizes()?
+        .into_iter()
+        .filter(|&size| size <= SIZE_MAX)
+        .sum())
+}
+
+fn part2(fs: &FileSystem) -> Result<usize> {
+    const SIZE_DISK: usize = 70_000_000;
+    const SIZE_UNUSED_DESIRED: usize = 30_000_000;
+
+    let sizes = fs.dir_sizes()?;
+    let used = sizes.first().copied().unwrap_or_default();
+    let unused = SIZE_DISK
+        .checked_sub(used)
+        .ok_or_else(|| anyhow!("files take up {used} bytes on a {SIZE_DISK} byte disk"))?;
+
+    let mut files = sizes.into_iter().map(Reverse).collect::<BinaryHeap<_>>();
+
+    while let Some(Reverse(size)) = files.pop() {
+        if unused + size >= SIZE_UNUSED_DESIRED {
+            return Ok(size);
+        }
+    }
+
+    Ok(used)
+}
+
+impl Solution for Day7 {
+    type Input = FileSystem;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        FileSystem::from_str(input)
+    }
+
+    fn part1(fs: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part1(fs)
+    }
+
+    fn part2(fs: &Self::Input) -> Result<impl std::fmt::Display> {
+        self::part2(fs)
+    }
+}
@@ -1,54 +1,40 @@
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::anyhow;
+use aoc_common::Grid;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
 
-type Pos = aoc_2022::Pos<usize>;
+pub struct Day8;
 
-struct HeightMap {
-    grid: Vec<u8>,
-    width: usize,
-    height: usize,
+type Pos = aoc_common::Pos<usize>;
+
+pub struct HeightMap {
+    heights: Grid<u8>,
 }
 
 impl FromStr for HeightMap {
     type Err = Error;
 
     fn from_str(map: &str) -> Result<Self> {
-        let grid = map
-            .lines()
-            .flat_map(|line| line.chars().map(|c| c.to_digit(10)))
-            .collect::<Option<Vec<_>>>()
-            .ok_or_else(|| anyhow!("invalid digit in height map"))?
-            .into_iter()
-            .map(u8::try_from)
-            .collect::<Result<_, _>>()?;
-
-        let width = map
-            .lines()
-            .next()
-            .ok_or_else(|| anyhow!("invalid height map"))?
-            .len();
-        let height = map.lines().count();
+        // Checked here rather than left to `Grid`, so that the error speaks of trees
+        let width = map.lines().next().map_or(0, |row| row.chars().count());
+
+        if let Some(row) = map.lines().find(|row| row.chars().count() != width) {
+            return Err(ParseError::new(row, format!("expected {width} trees")).into());
+        }
 
         Ok(Self {
-            grid,
-            width,
-            height,
+            heights: Grid::parse(map, |_, height| Ok(parse::from_str(height)?))?,
         })
     }
 }
 
 impl HeightMap {
     fn get(&self, pos: Pos) -> Option<u8> {
-        if pos.x() < self.width && pos.y() < self.height {
-            Some(self.grid[(self.width * pos.y()) + pos.x()])
-        } else {
-            None
-        }
+        self.heights.get(pos).copied()
     }
 
     fn is_visible(&self, pos: Pos) -> bool {
@@ -106,40 +92,34 @@ impl HeightMap {
 }
 
 fn part1(heights: &HeightMap) -> usize {
-    (0..heights.width)
-        .flat_map(|x| (0..heights.height).map(move |y| Pos::new(x, y)))
+    heights
+        .heights
+        .positions()
         .filter(|&pos| heights.is_visible(pos))
         .count()
 }
 
 fn part2(heights: &HeightMap) -> usize {
-    (0..heights.width)
-        .flat_map(|x| (0..heights.height).map(move |y| Pos::new(x, y)))
+    heights
+        .heights
+        .positions()
         .map(|pos| heights.score(pos))
         .max()
         .unwrap_or_default()
 }
 
-fn main() -> Result<()> {
-    let heights = HeightMap::from_str(&fs::read_to_string("in/day8.txt")?)?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::part1(&heights);
-        let elapsed = Instant::now().duration_since(start);
+impl Solution for Day8 {
+    type Input = HeightMap;
 
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 1_798);
-    };
-
-    {
-        let start = Instant::now();
-        let part2 = self::part2(&heights);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        HeightMap::from_str(input)
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 259_308);
-    };
+    fn part1(heights: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part1(heights))
+    }
 
-    Ok(())
+    fn part2(heights: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::part2(heights))
+    }
 }
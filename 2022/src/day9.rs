@@ -1,14 +1,16 @@
 use std::collections::HashSet;
-use std::fs;
 use std::str::FromStr;
-use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
-use anyhow::anyhow;
 use anyhow::bail;
+use aoc_common::Solution;
+use aoc_common::parse;
+use aoc_common::parse::ParseError;
 
-type Pos = aoc_2022::Pos<i64>;
+pub struct Day9;
+
+type Pos = aoc_common::Pos<i64>;
 
 #[derive(Clone, Copy, Debug)]
 enum Dir {
@@ -19,7 +21,7 @@ enum Dir {
 }
 
 #[derive(Debug)]
-struct Motion {
+pub struct Motion {
     dir: Dir,
     steps: usize,
 }
@@ -49,14 +51,10 @@ impl FromStr for Motion {
     fn from_str(motion: &str) -> Result<Self> {
         let (dir, steps) = motion
             .split_once(' ')
-            .ok_or_else(|| anyhow!("invalid motion '{motion}'"))?;
+            .ok_or_else(|| ParseError::new(motion, "invalid motion"))?;
 
-        let dir = dir
-            .chars()
-            .next()
-            .ok_or_else(|| anyhow!("invalid direction '{dir}'"))
-            .map(Dir::try_from)??;
-        let steps = steps.parse()?;
+        let dir = parse::char(dir)?;
+        let steps = parse::from_str(steps)?;
 
         Ok(Self { dir, steps })
     }
@@ -85,11 +83,9 @@ impl<const N: usize> Rope<N> {
 
         for i in 1..N {
             let (head, tail) = (self.knots[i - 1], self.knots[i]);
-            let dx = head.x() - tail.x();
-            let dy = head.y() - tail.y();
 
-            if dx.abs() > 1 || dy.abs() > 1 {
-                self.knots[i] = Pos::new(tail.x() + dx.signum(), tail.y() + dy.signum());
+            if head.chebyshev(tail) > 1 {
+                self.knots[i] += (head - tail).signum();
             }
         }
 
@@ -111,29 +107,18 @@ fn solve<const N: usize>(motions: &[Motion]) -> usize {
     visited.len()
 }
 
-fn main() -> Result<()> {
-    let motions = fs::read_to_string("in/day9.txt")?
-        .lines()
-        .map(Motion::from_str)
-        .collect::<Result<Vec<_>>>()?;
-
-    {
-        let start = Instant::now();
-        let part1 = self::solve::<2>(&motions);
-        let elapsed = Instant::now().duration_since(start);
-
-        println!("Part 1: {part1} ({elapsed:?})");
-        assert_eq!(part1, 5_960);
-    };
+impl Solution for Day9 {
+    type Input = Vec<Motion>;
 
-    {
-        let start = Instant::now();
-        let part2 = self::solve::<10>(&motions);
-        let elapsed = Instant::now().duration_since(start);
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(Motion::from_str).collect()
+    }
 
-        println!("Part 2: {part2} ({elapsed:?})");
-        assert_eq!(part2, 2_327);
-    };
+    fn part1(motions: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::solve::<2>(motions))
+    }
 
-    Ok(())
+    fn part2(motions: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(self::solve::<10>(motions))
+    }
 }
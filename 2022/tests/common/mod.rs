@@ -0,0 +1,14 @@
+//! Shared by the integration tests, each of which declares it as a module of its own.
+
+use std::path::Path;
+
+use aoc::input::Source;
+
+/// Reads the example input of `day`, checked in under `in/example`.
+pub fn example(day: u8) -> String {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    Source::Set("example".to_string())
+        .read(&root.join("in"), day)
+        .unwrap()
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::path::Path;
+
+use aoc::answers::Answers;
+use aoc::answers::Verdict;
+use aoc_2022::YEAR;
+use aoc_common::Part;
+
+fn check(day: u8) {
+    const INPUT: &str = "example";
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers = Answers::load(root.join("answers.toml")).unwrap();
+    let parsed = YEAR.get(day).unwrap().parse(&common::example(day)).unwrap();
+
+    for part in Part::ALL {
+        let answer = parsed.solve(part).unwrap();
+        let verdict = answers.verify(day, INPUT, part, &answer);
+
+        assert_eq!(verdict, Verdict::Pass, "day {day} part {part}: {answer}");
+    }
+}
+
+macro_rules! examples {
+    ($($name:ident => $day:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                self::check($day);
+            }
+        )*
+    };
+}
+
+// Day 15 is checked by its own unit tests, as its example uses different parameters.
+examples! {
+    day1 => 1,
+    day2 => 2,
+    day3 => 3,
+    day4 => 4,
+    day5 => 5,
+    day6 => 6,
+    day7 => 7,
+    day8 => 8,
+    day9 => 9,
+    day10 => 10,
+    day11 => 11,
+    day12 => 12,
+    day13 => 13,
+    day14 => 14,
+    day16 => 16,
+    day17 => 17,
+    day18 => 18,
+    day19 => 19,
+    day20 => 20,
+    day21 => 21,
+    day22 => 22,
+    day23 => 23,
+    day24 => 24,
+    day25 => 25,
+}
@@ -0,0 +1,43 @@
+use aoc_2022::YEAR;
+use aoc_common::Part;
+
+fn check(day: u8, input: &str, message: &str) {
+    let err = match YEAR.get(day).unwrap().parse(input) {
+        Ok(parsed) => Part::ALL
+            .into_iter()
+            .find_map(|part| parsed.solve(part).err())
+            .unwrap_or_else(|| panic!("day {day}: malformed input was solved")),
+        Err(err) => err,
+    };
+
+    assert!(err.to_string().contains(message), "day {day}: {err}");
+}
+
+macro_rules! malformed {
+    ($($name:ident => $day:literal, $input:expr, $message:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                self::check($day, &$input, $message);
+            }
+        )*
+    };
+}
+
+malformed! {
+    day3 => 3, "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n", "groups of 3",
+    day5 => 5, "    [D]\n[N] [C]\n 1   2\n\nmove 3 from 1 to 2\n", "cannot move 3 crates",
+    day7 => 7, "$ cd /\n$ cd ..\n", "already at the root directory",
+    day8 => 8, "303\n25\n", "expected 3 trees",
+    day10 => 10, "noop\naddx 3\n", "the program stops after 3 cycles",
+    day11 => 11, "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 0\n", "does not exist",
+    day11_to_itself => 11, "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 1\n    If false: throw to monkey 0\n\nMonkey 1:\n  Starting items: 1\n  Operation: new = old + 1\n  Test: divisible by 2\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n", "throws to itself",
+    day12 => 12, "Sbz\nabE\n", "no path from (0, 0)",
+    day13 => 13, format!("{}{}\n[1]\n", "[".repeat(1_000), "]".repeat(1_000)), "nested too deeply",
+    day16 => 16, "Valve AA has flow rate=0; tunnels lead to valves BB\n", "unknown valve 'BB'",
+    day17 => 17, "\n", "no jets of gas",
+    day20 => 20, "1\n2\n-3\n", "contains no 0",
+    day21 => 21, "root: aaaa + bbbb\naaaa: bbbb * cccc\nbbbb: aaaa - cccc\ncccc: 2\n", "cycle",
+    day24 => 24, "#.###\n#.#.#\n###.#\n", "no path from (1, 0) to (3, 2)",
+    day25 => 25, "2222222222222222222222222222222\n", "too large",
+}
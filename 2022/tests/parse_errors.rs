@@ -0,0 +1,41 @@
+use aoc_2022::YEAR;
+use aoc_common::parse::ParseError;
+
+fn check(day: u8, input: &str, (line, column): (usize, usize), snippet: &str) {
+    let Err(err) = YEAR.get(day).unwrap().parse(input) else {
+        panic!("day {day}: malformed input was accepted");
+    };
+    let err = err
+        .downcast_ref::<ParseError>()
+        .unwrap_or_else(|| panic!("day {day}: not a parse error: {err}"));
+
+    assert_eq!(err.day(), Some(day), "{err}");
+    assert_eq!(err.line(), Some(line), "{err}");
+    assert_eq!(err.column(), Some(column), "{err}");
+    assert_eq!(err.snippet(), snippet, "{err}");
+}
+
+macro_rules! parse_errors {
+    ($($name:ident => $day:literal, $input:literal, $location:expr, $snippet:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                self::check($day, $input, $location, $snippet);
+            }
+        )*
+    };
+}
+
+parse_errors! {
+    day1 => 1, "1000\n2000\n\n3x00\n", (4, 1), "3x00",
+    day2 => 2, "A Y\nB X\nC Q\n", (3, 3), "Q",
+    day4 => 4, "2-4,6-8\n2-3;4-5\n", (2, 1), "2-3;4-5",
+    day7 => 7, "$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ mv a b\n", (5, 3), "mv a b",
+    day10 => 10, "noop\naddx 3\njump 3\n", (3, 1), "jump",
+    day11 => 11, "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old ^ 19\n", (3, 24), "^",
+    day13 => 13, "[1,1,3]\n[1,x]\n", (2, 4), "x",
+    day14 => 14, "498,4 -> 498,6 -> 496,7\n", (1, 19), "496,7",
+    day16 => 16, "Valve AA has flow rate=1x; tunnels lead to valves BB\n", (1, 24), "1x",
+    day17 => 17, ">>><<><>^<<\n", (1, 9), "^",
+    day25 => 25, "1=-0-2\n12a11\n", (2, 3), "a",
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use aoc::render;
+use aoc::render::Canvas;
+use aoc::render::Palette;
+use aoc_2022::YEAR;
+use aoc_common::Part;
+use aoc_common::render::Cell;
+use aoc_common::render::Frame;
+
+/// Records the simulation of the example, rendering a frame every `every` steps.
+fn record(day: u8, part: Part, every: usize) -> Vec<Frame> {
+    let parsed = YEAR.get(day).unwrap().parse(&common::example(day)).unwrap();
+    let mut simulation = parsed.simulate(part).unwrap().unwrap();
+
+    render::record(simulation.as_mut(), every, 1_000_000).collect()
+}
+
+fn count(frame: &Frame, cell: Cell) -> usize {
+    frame.cells().iter().filter(|&(_, &c)| c == cell).count()
+}
+
+#[test]
+fn sand() {
+    let frames = self::record(14, Part::Two, 1);
+    let last = frames.last().unwrap();
+
+    assert_eq!(self::count(last, Cell::Filled), 93);
+    assert_eq!(self::count(last, Cell::Active), 0);
+    assert_eq!(self::count(&frames[0], Cell::Marker), 1);
+}
+
+#[test]
+fn tower() {
+    let frames = self::record(17, Part::One, 100);
+    let last = frames.last().unwrap();
+
+    // A window on the top of the tower, its bottom row staying put as the tower grows
+    assert!(
+        frames.iter().all(|frame| {
+            frame.origin().y() + i64::try_from(frame.cells().height()).unwrap() == 0
+        })
+    );
+    assert_eq!(frames[0].cells().height(), 1);
+    assert_eq!(last.cells().height(), 48);
+    assert!(frames.iter().all(|frame| frame.cells().height() <= 48));
+    assert!(self::count(last, Cell::Filled) > 0);
+}
+
+#[test]
+fn every() {
+    // The first frame, one every 5 of the 18 minutes and the last
+    assert_eq!(self::record(24, Part::One, 5).len(), 1 + 4);
+    assert_eq!(self::record(24, Part::One, 1).len(), 1 + 18);
+}
+
+#[test]
+fn canvas() {
+    for day in [14, 17, 22, 23, 24] {
+        let frames = self::record(day, Part::One, 1_000);
+        let canvas = Canvas::fit(&frames, 2, Palette::default()).unwrap();
+        let (width, height) = canvas.size();
+
+        assert!(
+            frames.iter().all(|frame| {
+                frame.cells().width() * 2 <= width && frame.cells().height() * 2 <= height
+            }),
+            "day {day}"
+        );
+
+        let mut png = Vec::new();
+        canvas.write_png(frames.last().unwrap(), &mut png).unwrap();
+        assert_eq!(png[1..4], *b"PNG", "day {day}");
+
+        let mut gif = Vec::new();
+        canvas.write_gif(&frames, 5, &mut gif).unwrap();
+        assert_eq!(gif[..6], *b"GIF89a", "day {day}");
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_2022::day7::FileSystem;
+use aoc_2022::day10::Program;
+use aoc_2022::day13::Pair;
+use aoc_2022::day14::Cave;
+use aoc_2022::day22::Board;
+use aoc_2022::day23::Grove;
+use aoc_2022::day24::Valley;
+
+/// Parsing the `Display` output of a parsed input gives back an equal input.
+fn check<T>(input: &str)
+where
+    T: FromStr + Display + PartialEq + Debug,
+    T::Err: Display,
+{
+    let parsed = input
+        .parse::<T>()
+        .unwrap_or_else(|err| panic!("{err}\n{input}"));
+    let rendered = parsed.to_string();
+    let reparsed = rendered
+        .parse::<T>()
+        .unwrap_or_else(|err| panic!("{err}\n{rendered}"));
+
+    assert_eq!(reparsed, parsed, "rendered as:\n{rendered}");
+}
+
+#[test]
+fn file_system() {
+    self::check::<FileSystem>(&common::example(7));
+}
+
+#[test]
+fn program() {
+    self::check::<Program>(&common::example(10));
+}
+
+#[test]
+fn pair() {
+    for pair in common::example(13).split("\n\n") {
+        self::check::<Pair>(pair);
+    }
+}
+
+#[test]
+fn cave() {
+    self::check::<Cave>(&common::example(14));
+}
+
+#[test]
+fn board() {
+    let input = common::example(22);
+    let (board, _) = input.split_once("\n\n").unwrap();
+
+    self::check::<Board>(board);
+}
+
+#[test]
+fn grove() {
+    self::check::<Grove>(&common::example(23));
+}
+
+#[test]
+fn valley() {
+    self::check::<Valley>(&common::example(24));
+}
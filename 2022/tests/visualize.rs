@@ -0,0 +1,85 @@
+mod common;
+
+use aoc_2022::YEAR;
+use aoc_common::Part;
+use aoc_common::visualize;
+
+/// Steps the simulation of the example until it finishes, returning the number of steps taken
+/// and the final frame.
+fn simulate(day: u8, part: Part) -> (usize, String) {
+    const LIMIT: usize = 1_000_000;
+
+    let parsed = YEAR.get(day).unwrap().parse(&common::example(day)).unwrap();
+    let mut simulation = parsed
+        .simulate(part)
+        .unwrap_or_else(|| panic!("day {day} has no visualization"))
+        .unwrap();
+    let steps = visualize::run(simulation.as_mut(), LIMIT);
+
+    assert!(steps < LIMIT, "day {day} part {part}: did not finish");
+
+    (steps, simulation.to_string())
+}
+
+/// The tile at the 1-based `row` and `col` of `frame`.
+fn tile(frame: &str, row: usize, col: usize) -> char {
+    frame
+        .lines()
+        .nth(row - 1)
+        .unwrap()
+        .chars()
+        .nth(col - 1)
+        .unwrap()
+}
+
+#[test]
+fn day14() {
+    let sand = |part| self::simulate(14, part).1.matches('o').count();
+
+    assert_eq!(sand(Part::One), 24);
+    assert_eq!(sand(Part::Two), 93);
+}
+
+#[test]
+fn day17() {
+    // Both parts show the first 2022 rocks, as a tower of 3068 rows above the floor
+    for part in Part::ALL {
+        assert_eq!(self::simulate(17, part).1.lines().count(), 3068 + 1);
+    }
+}
+
+#[test]
+fn day22() {
+    // The trail ends at the final position, pointing in the final direction
+    assert_eq!(self::tile(&self::simulate(22, Part::One).1, 6, 8), '>');
+    assert_eq!(self::tile(&self::simulate(22, Part::Two).1, 5, 7), '^');
+}
+
+#[test]
+fn day23() {
+    let (rounds, frame) = self::simulate(23, Part::One);
+    // The frame has a margin of empty ground around the elves
+    let (width, height) = (frame.lines().next().unwrap().len(), frame.lines().count());
+
+    assert_eq!(rounds, 10);
+    assert_eq!((width - 2) * (height - 2) - frame.matches('#').count(), 110);
+    // The 20th round is the first in which no elf moves
+    assert_eq!(self::simulate(23, Part::Two).0, 19);
+}
+
+#[test]
+fn day24() {
+    for (part, minutes) in [(Part::One, 18), (Part::Two, 54)] {
+        let (steps, frame) = self::simulate(24, part);
+
+        assert_eq!(steps, minutes);
+        assert_eq!(self::tile(&frame, 6, 7), 'E');
+    }
+}
+
+#[test]
+fn no_visualization() {
+    let parsed = YEAR.get(1).unwrap().parse("1\n").unwrap();
+
+    assert!(parsed.simulate(Part::One).is_none());
+}
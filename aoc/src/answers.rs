@@ -0,0 +1,153 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use anyhow::Error;
+use anyhow::Result;
+use aoc_common::Part;
+use toml::Table;
+use toml::Value;
+
+/// Expected answers, keyed by day, input name and part, e.g.:
+///
+/// ```toml
+/// [day1.default]
+/// part1 = 72_070
+/// part2 = 211_805
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    table: Table,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fail { expected } if expected.contains('\n') => {
+                write!(f, "{}, expected:\n{expected}", self.status())
+            }
+            Self::Fail { expected } => write!(f, "{}, expected {expected}", self.status()),
+            Self::Pass | Self::Unknown => write!(f, "{}", self.status()),
+        }
+    }
+}
+
+impl Verdict {
+    /// The verdict without the expected answer: `PASS`, `FAIL` or `UNKNOWN`.
+    #[must_use]
+    pub const fn status(&self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Fail { .. } => "FAIL",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    #[must_use]
+    pub fn expected(&self) -> Option<&str> {
+        match self {
+            Self::Fail { expected } => Some(expected),
+            Self::Pass | Self::Unknown => None,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(answers: &str) -> Result<Self> {
+        Ok(Self {
+            table: answers.parse()?,
+        })
+    }
+}
+
+impl Answers {
+    /// Loads the answers from `path`, treating a missing file as having no answers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid TOML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(answers) => answers
+                .parse()
+                .with_context(|| format!("invalid answers file '{}'", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("failed to read '{}'", path.display())),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, day: u8, input: &str, part: Part) -> Option<String> {
+        let answer = self
+            .table
+            .get(&format!("day{day}"))?
+            .get(input)?
+            .get(format!("part{part}"))?;
+
+        match answer {
+            Value::String(answer) => Some(answer.clone()),
+            answer => Some(answer.to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn verify(&self, day: u8, input: &str, part: Part, answer: &str) -> Verdict {
+        match self.get(day, input, part) {
+            Some(expected) if expected.trim_end() == answer.trim_end() => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify() {
+        let answers =
+            Answers::from_str("[day5.default]\npart1 = \"CMZ\"\n\n[day6.default]\npart1 = 1_238\n")
+                .unwrap();
+
+        assert_eq!(
+            answers.verify(5, "default", Part::One, "CMZ"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.verify(6, "default", Part::One, "1238"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.verify(6, "default", Part::One, "1237"),
+            Verdict::Fail {
+                expected: "1238".to_string()
+            }
+        );
+        assert_eq!(
+            answers.verify(6, "default", Part::Two, "0"),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.verify(6, "example", Part::One, "0"),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.verify(7, "default", Part::One, "0"),
+            Verdict::Unknown
+        );
+    }
+}
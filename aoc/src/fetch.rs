@@ -0,0 +1,297 @@
+//! Downloading puzzle inputs from Advent of Code, cached under the input directory.
+//!
+//! Inputs are personal, so requests are authenticated with the session cookie of a logged in
+//! browser, taken from `AOC_SESSION` or from a config file. To go easy on the servers, an input
+//! that is already cached is never downloaded again, a day is not requested before its puzzle
+//! unlocks, requests are spaced out when fetching several days, and being told to slow down
+//! stops the fetching rather than being retried. Requests say who sent them with a `User-Agent`,
+//! which should carry contact details, set with `AOC_USER_AGENT`.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+
+/// Fetches puzzle inputs with a session token.
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    user_agent: String,
+    /// The least time between two requests.
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+/// Where an input came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// Already in the cache, so nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+impl Client {
+    pub const BASE_URL: &str = "https://adventofcode.com";
+    /// The first year of Advent of Code.
+    pub const FIRST_YEAR: u16 = 2015;
+
+    /// The `User-Agent` sent unless another is set, which has no contact details.
+    pub const USER_AGENT: &str = concat!(
+        env!("CARGO_PKG_NAME"),
+        "/",
+        env!("CARGO_PKG_VERSION"),
+        " (aoc fetch)"
+    );
+
+    /// Creates a client for the site at `base_url`, e.g. [`Client::BASE_URL`] or a local mock.
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        const TIMEOUT: Duration = Duration::from_secs(30);
+
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(TIMEOUT))
+            .build()
+            .into();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            user_agent: Self::USER_AGENT.to_string(),
+            interval: Duration::from_secs(3),
+            last: None,
+        }
+    }
+
+    /// Creates a client for `AOC_BASE_URL`, or the real site when it is not set, with the
+    /// session token from [`self::session`] and the `User-Agent` from `AOC_USER_AGENT`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no session token.
+    pub fn from_env() -> Result<Self> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| Self::BASE_URL.to_string());
+        let client = Self::new(&base_url, &self::session()?);
+
+        Ok(match env::var("AOC_USER_AGENT") {
+            Ok(user_agent) if !user_agent.trim().is_empty() => client.user_agent(user_agent.trim()),
+            _ => client,
+        })
+    }
+
+    /// Sets the `User-Agent` of the requests, [`Client::USER_AGENT`] by default. Advent of Code
+    /// asks that it says how to reach whoever runs the tool, e.g.
+    /// `aoc fetch (you@example.com)`.
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Sets the least time between two requests, 3 seconds by default.
+    #[must_use]
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Downloads the puzzle input for `day` of `year`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the puzzle has not unlocked yet, the session token is rejected, the
+    /// server says there have been too many requests, or the request fails.
+    pub fn input(&mut self, year: u16, day: u8) -> Result<String> {
+        if year < Self::FIRST_YEAR {
+            bail!("there is no {year}, the first year is {}", Self::FIRST_YEAR);
+        }
+
+        if !(1..=25).contains(&day) {
+            bail!("there is no day {day}, only days 1 to 25");
+        }
+
+        if let Ok(wait) = self::unlock(year, day).duration_since(SystemTime::now()) {
+            let minutes = wait.as_secs().div_ceil(60);
+            bail!(
+                "day {day} unlocks in {}h {:02}m",
+                minutes / 60,
+                minutes % 60
+            );
+        }
+
+        if let Some(wait) = self
+            .last
+            .and_then(|last| self.interval.checked_sub(last.elapsed()))
+        {
+            thread::sleep(wait);
+        }
+        self.last = Some(Instant::now());
+
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", &self.user_agent)
+            .call()
+            .with_context(|| format!("failed to request {url}"))?;
+
+        match response.status().as_u16() {
+            200 => {}
+            400 | 401 | 403 => bail!("the session token was rejected, it may have expired"),
+            404 => bail!("no puzzle input for day {day} at {url}"),
+            429 => {
+                // Only the delay in seconds is understood, not the HTTP date it may also be
+                let retry = response
+                    .headers()
+                    .get("Retry-After")
+                    .and_then(|retry| retry.to_str().ok()?.trim().parse::<u64>().ok())
+                    .map_or_else(|| "later".to_string(), |secs| format!("in {secs}s"));
+
+                bail!("too many requests to {url}, try again {retry}");
+            }
+            status => bail!("{url} responded with status {status}"),
+        }
+
+        let input = response
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("failed to read the response from {url}"))?;
+
+        if input.trim().is_empty() {
+            bail!("the puzzle input for day {day} is empty");
+        }
+
+        Ok(input)
+    }
+}
+
+/// When the puzzle for `day` of `year` unlocks, at midnight EST.
+fn unlock(year: u16, day: u8) -> SystemTime {
+    // Midnight EST is 05:00 UTC
+    const HOUR: u64 = 60 * 60;
+
+    let leap = |year: u16| {
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+    };
+    let days = |year: u16| if leap(year) { 366 } else { 365 };
+    // Days since the epoch until December 1st, which is 334 days into a common year
+    let december = (1970..year).map(days).sum::<u64>() + 334 + u64::from(leap(year));
+
+    let secs = (december + u64::from(day) - 1) * 24 * HOUR + 5 * HOUR;
+
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// The session token, from `AOC_SESSION` or else the file at `AOC_SESSION_FILE`, which defaults
+/// to `aoc/session` in the config directory (`$XDG_CONFIG_HOME`, or `~/.config`).
+///
+/// The token is the value of the `session` cookie set by adventofcode.com once logged in.
+///
+/// # Errors
+///
+/// Returns an error if neither is set, or the file cannot be read.
+pub fn session() -> Result<String> {
+    if let Ok(session) = env::var("AOC_SESSION")
+        && !session.trim().is_empty()
+    {
+        return Ok(self::token(&session));
+    }
+
+    let path = env::var_os("AOC_SESSION_FILE")
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env::home_dir().map(|home| home.join(".config")))
+                .map(|config| config.join("aoc").join("session"))
+        })
+        .ok_or_else(|| anyhow!("no session token: set AOC_SESSION or AOC_SESSION_FILE"))?;
+
+    let session = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "no session token: set AOC_SESSION, or save it to '{}'",
+            path.display()
+        )
+    })?;
+
+    Ok(self::token(&session))
+}
+
+/// The token alone, whether given as `session=<token>` or just `<token>`.
+fn token(session: &str) -> String {
+    let session = session.trim();
+
+    session
+        .strip_prefix("session=")
+        .unwrap_or(session)
+        .to_string()
+}
+
+/// Downloads the input for `day` of `year` into `<dir>/dayN.txt`, unless it is already there.
+///
+/// An empty file counts as missing, such as the one left by `aoc new` when fetching fails.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be downloaded or written.
+pub fn fetch(client: &mut Client, dir: &Path, year: u16, day: u8) -> Result<Fetched> {
+    let path = dir.join(format!("day{day}.txt"));
+
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.input(year, day)?;
+
+    fs::create_dir_all(dir).with_context(|| format!("failed to create '{}'", dir.display()))?;
+
+    // Written aside first, so that a failed write does not look like a cached input
+    let partial = path.with_extension("txt.partial");
+    fs::write(&partial, input)
+        .and_then(|()| fs::rename(&partial, &path))
+        .with_context(|| format!("failed to write '{}'", path.display()))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlock() {
+        // Day 1 unlocked at 2022-12-01T05:00:00Z, day 25 at 2022-12-25T05:00:00Z
+        let secs = |year, day| {
+            super::unlock(year, day)
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        };
+
+        assert_eq!(secs(2022, 1), 1_669_870_800);
+        assert_eq!(secs(2022, 25), 1_671_944_400);
+        // 2024 is a leap year, 2015 the first year
+        assert_eq!(secs(2024, 1), 1_733_029_200);
+        assert_eq!(secs(2015, 1), 1_448_946_000);
+    }
+
+    #[test]
+    fn token() {
+        assert_eq!(super::token("53616c7465645f5f\n"), "53616c7465645f5f");
+        assert_eq!(super::token("session=53616c7465645f5f"), "53616c7465645f5f");
+    }
+}
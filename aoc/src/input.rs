@@ -0,0 +1,65 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// A named input set, read from `<dir>/dayN.txt` for the default set and from
+    /// `<dir>/<name>/dayN.txt` otherwise.
+    Set(String),
+    /// A single file, used regardless of the day.
+    File(PathBuf),
+    Stdin,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Set(name) => write!(f, "{name}"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "-"),
+        }
+    }
+}
+
+impl Source {
+    pub const DEFAULT: &str = "default";
+
+    /// The name expected answers are recorded under, if any.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Set(name) => Some(name),
+            Self::File(_) | Self::Stdin => None,
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self, dir: &Path, day: u8) -> Option<PathBuf> {
+        match self {
+            Self::Set(name) if name == Self::DEFAULT => Some(dir.join(format!("day{day}.txt"))),
+            Self::Set(name) => Some(dir.join(name).join(format!("day{day}.txt"))),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin => None,
+        }
+    }
+
+    /// Reads the input for `day`, resolving input sets relative to `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input cannot be read.
+    pub fn read(&self, dir: &Path, day: u8) -> Result<String> {
+        let Some(path) = self.path(dir, day) else {
+            return io::read_to_string(io::stdin()).context("failed to read input from stdin");
+        };
+
+        fs::read_to_string(&path)
+            .with_context(|| format!("failed to read input '{}'", path.display()))
+    }
+}
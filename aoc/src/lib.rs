@@ -0,0 +1,15 @@
+//! What the runner needs beyond the solutions: reading the inputs and checking the answers,
+//! fetching the inputs and scaffolding new days, and playing back or rendering the simulations.
+//!
+//! These are kept out of `aoc-common`, so that a year's crate does not depend on the HTTP
+//! client, the terminal or the image encoders they bring in.
+
+// TLS for `aoc fetch` brings in an older windows-sys through ring, for Windows only
+#![allow(clippy::multiple_crate_versions)]
+
+pub mod answers;
+pub mod fetch;
+pub mod input;
+pub mod render;
+pub mod scaffold;
+pub mod visualize;
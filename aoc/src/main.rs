@@ -0,0 +1,698 @@
+// TLS for `aoc fetch` brings in an older windows-sys through ring, for Windows only
+#![allow(clippy::multiple_crate_versions)]
+
+use std::fs;
+use std::fs::File;
+use std::io::BufWriter;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use aoc::answers::Answers;
+use aoc::answers::Verdict;
+use aoc::fetch;
+use aoc::fetch::Client;
+use aoc::fetch::Fetched;
+use aoc::input::Source;
+use aoc::render;
+use aoc::render::Canvas;
+use aoc::render::Palette;
+use aoc::scaffold;
+use aoc_common::Day;
+use aoc_common::Parsed;
+use aoc_common::Part;
+use aoc_common::Year;
+use clap::Args;
+use clap::Parser;
+use clap::Subcommand;
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Every year with solutions, in ascending order.
+const YEARS: &[Year] = &[aoc_2022::YEAR];
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the solutions for one or more days
+    Run(RunArgs),
+    /// Play a day's simulation step by step in the terminal
+    Visualize(VisualizeArgs),
+    /// Render a day's simulation to PNG frames or an animated GIF
+    Render(RenderArgs),
+    /// Download puzzle inputs into the input directory, unless already there
+    Fetch(FetchArgs),
+    /// Scaffold a new day's solution and fetch its input
+    New(NewArgs),
+}
+
+#[derive(Debug, Args)]
+struct RenderArgs {
+    /// Part to render
+    #[arg(long, default_value = "1")]
+    part: Part,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// An animated GIF if the path ends in `.gif`, otherwise a directory of numbered PNG frames
+    #[arg(long, short)]
+    output: PathBuf,
+
+    /// Width and height of a cell in pixels
+    #[arg(long, default_value_t = 4)]
+    cell_size: usize,
+
+    /// Colours to change from the default palette, e.g. `wall=#ffffff,filled=#c2b280`; the cells
+    /// are void, empty, wall, filled, active and marker
+    #[arg(long, default_value = "")]
+    palette: Palette,
+
+    /// Render a frame every N steps
+    #[arg(long, default_value_t = 1)]
+    every: usize,
+
+    /// Stop after this many steps, if the simulation has not finished
+    #[arg(long, default_value_t = 100_000)]
+    limit: usize,
+
+    /// Frames per second of the GIF
+    #[arg(long, default_value_t = 20)]
+    fps: u16,
+
+    /// Year (defaults to the latest), then the day to render
+    #[arg(value_name = "[YEAR] DAY", required = true, num_args = 1..=2)]
+    targets: Vec<Target>,
+}
+
+#[derive(Debug, Args)]
+struct FetchArgs {
+    /// Directory to save the inputs in, as `<INPUT_DIR>/dayN.txt` [default: <YEAR>/in]
+    #[arg(long, env = "AOC_INPUT_DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Year (defaults to the latest), then the days to fetch, e.g. `17`, `1..=25` or `1..5`
+    #[arg(value_name = "[YEAR] DAYS", required = true)]
+    targets: Vec<Target>,
+}
+
+#[derive(Debug, Args)]
+struct NewArgs {
+    /// Root of the year's crate to add the day to [default: <YEAR>]
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Directory to save the input in, as `<INPUT_DIR>/dayN.txt` [default: <YEAR>/in]
+    #[arg(long, env = "AOC_INPUT_DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Year (defaults to the latest), then the day to scaffold
+    #[arg(value_name = "[YEAR] DAY", required = true, num_args = 1..=2)]
+    targets: Vec<Target>,
+}
+
+/// Where to read the puzzle input from.
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Named input set, read from `<INPUT_DIR>/<INPUT>/dayN.txt`
+    #[arg(long, default_value = Source::DEFAULT)]
+    input: String,
+
+    /// Read the input of a single day from a file instead of an input set, or from stdin with `-`
+    #[arg(long, conflicts_with = "input")]
+    file: Option<PathBuf>,
+
+    /// Directory containing the input sets [default: <YEAR>/in]
+    #[arg(long, env = "AOC_INPUT_DIR")]
+    input_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Expected answers to verify against [default: <YEAR>/answers.toml]
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Only run the given part
+    #[arg(long)]
+    part: Option<Part>,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Print how the input executes, step by step, for the days that can trace it (such as day
+    /// 10 of 2022, cycle by cycle)
+    #[arg(long, conflicts_with = "format")]
+    trace: bool,
+
+    /// Year (defaults to the latest), then the days to run, e.g. `17`, `1..=25` or `1..5`
+    /// (defaults to every day)
+    #[arg(value_name = "[YEAR] DAYS")]
+    targets: Vec<Target>,
+}
+
+#[derive(Debug, Args)]
+struct VisualizeArgs {
+    /// Part to visualize
+    #[arg(long, default_value = "1")]
+    part: Part,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Initial speed in steps per second
+    #[arg(long, default_value_t = 10)]
+    speed: u32,
+
+    /// Year (defaults to the latest), then the day to visualize
+    #[arg(value_name = "[YEAR] DAY", required = true, num_args = 1..=2)]
+    targets: Vec<Target>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Human-readable report
+    Text,
+    /// One JSON object per line for each day and part
+    Json,
+}
+
+/// A single answer, as emitted by `--format json`.
+#[derive(Clone, Copy, Debug, Serialize)]
+struct Report<'a> {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Option<&'a str>,
+    parse_ns: Option<u128>,
+    solve_ns: Option<u128>,
+    /// The input file, or `-` for stdin.
+    input: &'a str,
+    /// `PASS`, `FAIL` or `UNKNOWN`, or `ERROR` if the input could not be read, parsed or solved.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// Inputs or parts that could not be run, and answers that did not match the expected answers.
+#[derive(Clone, Copy, Debug, Default)]
+struct Tally {
+    errors: usize,
+    failures: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Days {
+    range: RangeInclusive<u8>,
+}
+
+/// A positional argument naming either a year, or one or more days of it.
+#[derive(Clone, Debug)]
+enum Target {
+    Year(u16),
+    Days(Days),
+}
+
+/// The year and days named by the positional arguments, as in `aoc run 2022 1..=5`.
+#[derive(Clone, Debug)]
+struct Selection {
+    year: Option<u16>,
+    days: Vec<u8>,
+}
+
+impl FromStr for Days {
+    type Err = Error;
+
+    fn from_str(days: &str) -> Result<Self> {
+        let parse = |day: &str| {
+            day.parse::<u8>()
+                .with_context(|| format!("invalid day '{day}'"))
+        };
+
+        let range = if let Some((start, end)) = days.split_once("..=") {
+            parse(start)?..=parse(end)?
+        } else if let Some((start, end)) = days.split_once("..") {
+            let end = parse(end)?
+                .checked_sub(1)
+                .ok_or_else(|| anyhow!("empty range '{days}'"))?;
+            parse(start)?..=end
+        } else {
+            let day = parse(days)?;
+            day..=day
+        };
+
+        if range.is_empty() {
+            bail!("empty range '{days}'");
+        }
+
+        Ok(Self { range })
+    }
+}
+
+impl FromStr for Target {
+    type Err = Error;
+
+    fn from_str(target: &str) -> Result<Self> {
+        // Days only go up to 25, so any larger number must be a year
+        match target.parse::<u16>() {
+            Ok(year) if year >= Client::FIRST_YEAR => Ok(Self::Year(year)),
+            _ => target.parse().map(Self::Days),
+        }
+    }
+}
+
+impl Selection {
+    fn new(targets: &[Target]) -> Result<Self> {
+        let (year, days) = match targets {
+            [Target::Year(year), days @ ..] => (Some(*year), days),
+            days => (None, days),
+        };
+
+        let days = days
+            .iter()
+            .map(|target| match target {
+                Target::Year(year) => bail!("the year {year} must come before the days"),
+                Target::Days(days) => Ok(days.range.clone()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            year,
+            days: days.into_iter().flatten().collect(),
+        })
+    }
+
+    /// The selected year, or the latest one if none was given.
+    fn year(&self) -> u16 {
+        self.year.unwrap_or(YEARS[YEARS.len() - 1].year())
+    }
+
+    /// The selected year's solutions.
+    fn solutions(&self) -> Result<Year> {
+        let year = self.year();
+
+        YEARS
+            .iter()
+            .copied()
+            .find(|y| y.year() == year)
+            .ok_or_else(|| anyhow!("no solutions for {year}"))
+    }
+
+    /// The selected days, or every day of the year if none were given.
+    fn days(&self) -> Result<Vec<Day>> {
+        let year = self.solutions()?;
+
+        if self.days.is_empty() {
+            return Ok(year.days().to_vec());
+        }
+
+        self.days
+            .iter()
+            .map(|&day| {
+                year.get(day)
+                    .ok_or_else(|| anyhow!("no solution for day {day} of {}", year.year()))
+            })
+            .collect()
+    }
+
+    /// The single selected day.
+    fn day(&self) -> Result<u8> {
+        match self.days[..] {
+            [day] => Ok(day),
+            _ => bail!("expected a single day"),
+        }
+    }
+}
+
+impl InputArgs {
+    fn source(&self) -> Source {
+        match self.file.as_deref() {
+            Some(path) if path == Path::new("-") => Source::Stdin,
+            Some(path) => Source::File(path.to_path_buf()),
+            None => Source::Set(self.input.clone()),
+        }
+    }
+}
+
+/// The crate of `year` in the workspace the runner was built in, so that it is found from
+/// wherever the runner is run.
+fn year_dir(year: u16) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    manifest_dir
+        .parent()
+        .unwrap_or(manifest_dir)
+        .join(year.to_string())
+}
+
+/// The input directory given, or else the year's own.
+fn input_dir(input_dir: Option<&Path>, year: u16) -> PathBuf {
+    input_dir.map_or_else(|| self::year_dir(year).join("in"), Path::to_path_buf)
+}
+
+fn run(args: &RunArgs) -> Result<()> {
+    let selection = Selection::new(&args.targets)?;
+    let year = selection.year();
+    let days = selection.days()?;
+    let answers = Answers::load(
+        args.answers
+            .clone()
+            .unwrap_or_else(|| self::year_dir(year).join("answers.toml")),
+    )?;
+    let source = args.input.source();
+    let input_dir = self::input_dir(args.input.input_dir.as_deref(), year);
+
+    // A single input belongs to a single day, rather than being fed to every day
+    match &source {
+        Source::Stdin if days.len() > 1 => {
+            bail!("input from stdin can only be used with a single day");
+        }
+        Source::File(path) if days.len() > 1 => {
+            bail!(
+                "input from '{}' can only be used with a single day",
+                path.display()
+            );
+        }
+        _ => {}
+    }
+
+    let mut tally = Tally::default();
+
+    for (i, day) in days.into_iter().enumerate() {
+        if args.format == Format::Text && i > 0 {
+            println!();
+        }
+
+        self::run_day(
+            args,
+            &answers,
+            (&source, &input_dir),
+            (year, day),
+            &mut tally,
+        )?;
+    }
+
+    let Tally { errors, failures } = tally;
+
+    match (errors, failures) {
+        (0, 0) => Ok(()),
+        (_, 0) => bail!("{errors} error(s) while running the solutions"),
+        (0, _) => bail!("{failures} answer(s) did not match the expected answers"),
+        _ => bail!(
+            "{errors} error(s) while running the solutions, and {failures} answer(s) did not match \
+             the expected answers"
+        ),
+    }
+}
+
+/// Runs a single day, reporting errors alongside the answers rather than returning them.
+fn run_day(
+    args: &RunArgs,
+    answers: &Answers,
+    (source, input_dir): (&Source, &Path),
+    (year, day): (u16, Day),
+    tally: &mut Tally,
+) -> Result<()> {
+    let parts = Part::ALL
+        .into_iter()
+        .filter(|&part| args.part.is_none_or(|p| p == part));
+    let path = source.path(input_dir, day.day()).map_or_else(
+        || Source::Stdin.to_string(),
+        |path| path.display().to_string(),
+    );
+    let report = Report {
+        year,
+        day: day.day(),
+        part: 0,
+        answer: None,
+        parse_ns: None,
+        solve_ns: None,
+        input: &path,
+        status: "ERROR",
+        expected: None,
+        error: None,
+    };
+
+    if args.format == Format::Text {
+        println!("Day {}", day.day());
+    }
+
+    let start = Instant::now();
+    let parsed = source
+        .read(input_dir, day.day())
+        .and_then(|input| day.parse(&input));
+    let parse_elapsed = start.elapsed();
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let err = format!("{err:#}");
+            tally.errors += 1;
+
+            match args.format {
+                Format::Text => println!("Error: {err}"),
+                Format::Json => {
+                    for part in parts {
+                        self::emit(&Report {
+                            part: part.into(),
+                            error: Some(&err),
+                            ..report
+                        })?;
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+    };
+
+    if args.format == Format::Text {
+        println!("Parse ({parse_elapsed:?})");
+    }
+
+    if args.trace {
+        self::trace(parsed.as_ref());
+    }
+
+    for part in parts {
+        let start = Instant::now();
+        let answer = parsed.solve(part);
+        let elapsed = start.elapsed();
+
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(err) => {
+                let err = format!("{err:#}");
+                tally.errors += 1;
+
+                match args.format {
+                    Format::Text => println!("Part {part} ({elapsed:?}) ERROR: {err}"),
+                    Format::Json => self::emit(&Report {
+                        part: part.into(),
+                        parse_ns: Some(parse_elapsed.as_nanos()),
+                        solve_ns: Some(elapsed.as_nanos()),
+                        error: Some(&err),
+                        ..report
+                    })?,
+                }
+
+                continue;
+            }
+        };
+
+        let verdict = source.name().map_or(Verdict::Unknown, |name| {
+            answers.verify(day.day(), name, part, &answer)
+        });
+
+        match args.format {
+            Format::Text if answer.contains('\n') => {
+                println!("Part {part} ({elapsed:?}) {verdict}:");
+                println!("{answer}");
+            }
+            Format::Text => println!("Part {part}: {answer} ({elapsed:?}) {verdict}"),
+            Format::Json => self::emit(&Report {
+                part: part.into(),
+                answer: Some(&answer),
+                parse_ns: Some(parse_elapsed.as_nanos()),
+                solve_ns: Some(elapsed.as_nanos()),
+                status: verdict.status(),
+                expected: verdict.expected(),
+                ..report
+            })?,
+        }
+
+        if matches!(verdict, Verdict::Fail { .. }) {
+            tally.failures += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads and parses the input of the single day named by `targets`.
+fn parse(targets: &[Target], input: &InputArgs) -> Result<(Day, Box<dyn Parsed>)> {
+    let selection = Selection::new(targets)?;
+    let [day] = selection.days()?[..] else {
+        bail!("expected a single day");
+    };
+    let input_dir = self::input_dir(input.input_dir.as_deref(), selection.year());
+    let parsed = day.parse(&input.source().read(&input_dir, day.day())?)?;
+
+    Ok((day, parsed))
+}
+
+/// Prints each step of the input's execution, for `--trace`.
+fn trace(parsed: &dyn Parsed) {
+    match parsed.trace() {
+        Some(trace) => trace.for_each(|step| println!("{step}")),
+        None => println!("No trace"),
+    }
+}
+
+fn visualize(args: &VisualizeArgs) -> Result<()> {
+    let (day, parsed) = self::parse(&args.targets, &args.input)?;
+    let mut simulation = parsed
+        .simulate(args.part)
+        .ok_or_else(|| anyhow!("day {} has no visualization", day.day()))??;
+
+    aoc::visualize::play(simulation.as_mut(), args.speed)
+}
+
+fn render(args: &RenderArgs) -> Result<()> {
+    let (day, parsed) = self::parse(&args.targets, &args.input)?;
+    let simulate = || {
+        parsed
+            .simulate(args.part)
+            .ok_or_else(|| anyhow!("day {} has no visualization", day.day()))?
+    };
+
+    // The frames are not kept, so a first run only finds the area they cover and how many there
+    // are, and a second one renders them again to write them out
+    let mut count = 0;
+    let canvas = Canvas::fit(
+        render::record(simulate()?.as_mut(), args.every, args.limit).inspect(|_| count += 1),
+        args.cell_size,
+        args.palette,
+    )?;
+    let mut simulation = simulate()?;
+    let frames = render::record(simulation.as_mut(), args.every, args.limit);
+    let create = |path: &Path| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+
+        File::create(path)
+            .map(BufWriter::new)
+            .with_context(|| format!("failed to create {}", path.display()))
+    };
+
+    if args.output.extension().is_some_and(|ext| ext == "gif") {
+        let delay = (100 / args.fps.max(1)).max(1);
+
+        canvas.write_gif(frames, delay, create(&args.output)?)?;
+    } else {
+        for (i, frame) in frames.enumerate() {
+            let path = args.output.join(format!("frame-{i:05}.png"));
+            canvas.write_png(&frame, create(&path)?)?;
+        }
+    }
+
+    let (width, height) = canvas.size();
+    println!(
+        "Rendered {count} {width}x{height} frames to {}",
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+fn fetch(args: &FetchArgs) -> Result<()> {
+    let selection = Selection::new(&args.targets)?;
+    let year = selection.year();
+    let input_dir = self::input_dir(args.input_dir.as_deref(), year);
+    let mut client = Client::from_env()?;
+
+    for &day in &selection.days {
+        match fetch::fetch(&mut client, &input_dir, year, day)
+            .with_context(|| format!("failed to fetch day {day}"))?
+        {
+            Fetched::Cached(path) => println!("Day {day}: already in {}", path.display()),
+            Fetched::Downloaded(path) => println!("Day {day}: saved to {}", path.display()),
+        }
+    }
+
+    Ok(())
+}
+
+fn new(args: &NewArgs) -> Result<()> {
+    let selection = Selection::new(&args.targets)?;
+    let (year, day) = (selection.year(), selection.day()?);
+
+    if !(1..=25).contains(&day) {
+        bail!("there is no day {day}, only days 1 to 25");
+    }
+
+    // Not looked up in `YEARS`, so that days can be added to a year not yet built into the runner
+    let root = args.root.clone().unwrap_or_else(|| self::year_dir(year));
+
+    for path in scaffold::create(&root, day, year)? {
+        println!("Wrote {}", path.display());
+    }
+
+    let input_dir = args.input_dir.clone().unwrap_or_else(|| root.join("in"));
+    let fetched =
+        Client::from_env().and_then(|mut client| fetch::fetch(&mut client, &input_dir, year, day));
+
+    match fetched {
+        Ok(Fetched::Cached(path)) => println!("Input already in {}", path.display()),
+        Ok(Fetched::Downloaded(path)) => println!("Saved input to {}", path.display()),
+        Err(err) => {
+            let path = input_dir.join(format!("day{day}.txt"));
+            fs::create_dir_all(&input_dir)?;
+            File::options().create(true).append(true).open(&path)?;
+
+            println!(
+                "Could not fetch the input, fill in {} by hand: {err:#}",
+                path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn emit(report: &Report) -> Result<()> {
+    println!("{}", serde_json::to_string(report)?);
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Run(args) => self::run(&args),
+        Command::Visualize(args) => self::visualize(&args),
+        Command::Render(args) => self::render(&args),
+        Command::Fetch(args) => self::fetch(&args),
+        Command::New(args) => self::new(&args),
+    }
+}
@@ -0,0 +1,399 @@
+//! Rendering of the puzzle simulations to PNG frames or an animated GIF.
+//!
+//! A [`Canvas`] colours the [`Cell`]s of the frames drawn by a [`Render`] type with a
+//! [`Palette`] and scales them up to squares of pixels. The frames are rendered one at a time by
+//! [`record`] and written out as they come, as a long simulation has far too many to keep.
+//!
+//! [`Render`]: aoc_common::render::Render
+
+use std::borrow::Borrow;
+use std::borrow::Cow;
+use std::io::Write;
+use std::str::FromStr;
+
+use anyhow::Context;
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use aoc_common::Pos;
+use aoc_common::render::Cell;
+use aoc_common::render::Frame;
+use aoc_common::visualize::Simulation;
+
+/// The colour of each kind of cell, as RGB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette([[u8; 3]; Cell::ALL.len()]);
+
+/// The area covered by a sequence of frames, and how to turn their cells into pixels.
+#[derive(Clone, Copy, Debug)]
+pub struct Canvas {
+    origin: Pos<i64>,
+    /// One past the bottom right cell.
+    end: Pos<i64>,
+    /// Size in cells.
+    width: usize,
+    height: usize,
+    /// Width and height of a cell in pixels.
+    cell_size: usize,
+    palette: Palette,
+}
+
+impl Palette {
+    #[must_use]
+    pub const fn color(&self, cell: Cell) -> [u8; 3] {
+        self.0[cell as usize]
+    }
+
+    pub const fn set(&mut self, cell: Cell, color: [u8; 3]) {
+        self.0[cell as usize] = color;
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self([
+            [0x10, 0x10, 0x14],
+            [0x1e, 0x1e, 0x28],
+            [0x8c, 0x8c, 0x96],
+            [0xe6, 0xc3, 0x5a],
+            [0x5a, 0xb4, 0xe6],
+            [0xe6, 0x5a, 0x5a],
+        ])
+    }
+}
+
+/// Parses colours to change from the default palette, e.g. `wall=#ffffff,filled=#c2b280`.
+impl FromStr for Palette {
+    type Err = Error;
+
+    fn from_str(colors: &str) -> Result<Self> {
+        let mut palette = Self::default();
+
+        for color in colors.split(',').filter(|color| !color.is_empty()) {
+            let (cell, hex) = color
+                .split_once('=')
+                .ok_or_else(|| anyhow!("expected '<cell>=#rrggbb', got '{color}'"))?;
+            let rgb = hex
+                .strip_prefix('#')
+                .filter(|rgb| rgb.len() == 6)
+                .and_then(|rgb| u32::from_str_radix(rgb, 16).ok())
+                .ok_or_else(|| anyhow!("invalid colour '{hex}', expected '#rrggbb'"))?;
+            let [_, r, g, b] = rgb.to_be_bytes();
+
+            palette.set(cell.parse()?, [r, g, b]);
+        }
+
+        Ok(palette)
+    }
+}
+
+impl Canvas {
+    /// A canvas covering every cell of `frames`, which are only looked at one at a time, so that
+    /// they can be rendered again to be written rather than all kept in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no frames, or the canvas would have no pixels.
+    pub fn fit(
+        frames: impl IntoIterator<Item = impl Borrow<Frame>>,
+        cell_size: usize,
+        palette: Palette,
+    ) -> Result<Self> {
+        if cell_size == 0 {
+            bail!("the cell size must be at least 1 pixel");
+        }
+
+        let corners = |frame: &Frame| {
+            let end = |len: usize| i64::try_from(len).unwrap_or(i64::MAX);
+            let origin = frame.origin();
+
+            (
+                origin,
+                origin + Pos::new(end(frame.cells().width()), end(frame.cells().height())),
+            )
+        };
+        let (min, max) = frames
+            .into_iter()
+            .map(|frame| corners(frame.borrow()))
+            .reduce(|(min, max), (start, end)| {
+                (
+                    Pos::new(min.x().min(start.x()), min.y().min(start.y())),
+                    Pos::new(max.x().max(end.x()), max.y().max(end.y())),
+                )
+            })
+            .ok_or_else(|| anyhow!("no frames to render"))?;
+
+        let canvas = Self {
+            origin: min,
+            end: max,
+            width: usize::try_from(max.x() - min.x())?,
+            height: usize::try_from(max.y() - min.y())?,
+            cell_size,
+            palette,
+        };
+
+        if canvas.width == 0 || canvas.height == 0 {
+            bail!("the frames have no cells");
+        }
+
+        Ok(canvas)
+    }
+
+    /// The size of the canvas in pixels.
+    #[must_use]
+    pub const fn size(&self) -> (usize, usize) {
+        (self.width * self.cell_size, self.height * self.cell_size)
+    }
+
+    /// The palette index of each pixel of `frame`, row by row.
+    fn pixels(&self, frame: &Frame) -> Vec<u8> {
+        let (width, height) = self.size();
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for y in self.origin.y()..self.end.y() {
+            let row = (self.origin.x()..self.end.x())
+                .flat_map(|x| std::iter::repeat_n(frame.get(Pos::new(x, y)) as u8, self.cell_size))
+                .collect::<Vec<_>>();
+
+            for _ in 0..self.cell_size {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        pixels
+    }
+
+    fn palette(&self) -> Vec<u8> {
+        Cell::ALL
+            .into_iter()
+            .flat_map(|cell| self.palette.color(cell))
+            .collect()
+    }
+
+    /// Writes `frame` as an indexed-colour PNG image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is too large for a PNG, or cannot be written.
+    pub fn write_png(&self, frame: &Frame, mut out: impl Write) -> Result<()> {
+        const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+        let (width, height) = self.size();
+        let too_large = || anyhow!("a {width}x{height} image is too large for a PNG");
+        let mut header = Vec::with_capacity(13);
+        header.extend(u32::try_from(width).map_err(|_| too_large())?.to_be_bytes());
+        header.extend(
+            u32::try_from(height)
+                .map_err(|_| too_large())?
+                .to_be_bytes(),
+        );
+        // 8 bits per pixel, indexed colour, no interlacing
+        header.extend([8, 3, 0, 0, 0]);
+
+        // Every scanline starts with its filter type, which is always none
+        let pixels = self.pixels(frame);
+        let scanlines = pixels
+            .chunks(width)
+            .flat_map(|row| std::iter::once(0).chain(row.iter().copied()))
+            .collect::<Vec<_>>();
+        let data = miniz_oxide::deflate::compress_to_vec_zlib(&scanlines, 6);
+
+        out.write_all(&SIGNATURE)?;
+        self::chunk(&mut out, *b"IHDR", &header)?;
+        self::chunk(&mut out, *b"PLTE", &self.palette())?;
+        self::chunk(&mut out, *b"IDAT", &data)?;
+        self::chunk(&mut out, *b"IEND", &[])?;
+
+        Ok(())
+    }
+
+    /// Writes `frames` as an animated GIF that loops forever, showing each frame for `delay`
+    /// hundredths of a second. Each frame is encoded as soon as it is taken from `frames`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is too large for a GIF, or cannot be written.
+    pub fn write_gif(
+        &self,
+        frames: impl IntoIterator<Item = impl Borrow<Frame>>,
+        delay: u16,
+        out: impl Write,
+    ) -> Result<()> {
+        let (width, height) = self.size();
+        let too_large = || anyhow!("a {width}x{height} image is too large for a GIF");
+        let width = u16::try_from(width).map_err(|_| too_large())?;
+        let height = u16::try_from(height).map_err(|_| too_large())?;
+
+        let mut encoder = gif::Encoder::new(out, width, height, &self.palette())?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        for frame in frames {
+            encoder.write_frame(&gif::Frame {
+                width,
+                height,
+                delay,
+                buffer: Cow::Owned(self.pixels(frame.borrow())),
+                ..gif::Frame::default()
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a PNG chunk, followed by the CRC-32 of its type and data.
+fn chunk(out: &mut impl Write, kind: [u8; 4], data: &[u8]) -> Result<()> {
+    let len = u32::try_from(data.len()).context("PNG chunk too large")?;
+
+    out.write_all(&len.to_be_bytes())?;
+    out.write_all(&kind)?;
+    out.write_all(data)?;
+    out.write_all(&self::crc32(kind.iter().chain(data)).to_be_bytes())?;
+
+    Ok(())
+}
+
+/// The CRC-32 used by PNG, computed bit by bit as the images are small.
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    const POLYNOMIAL: u32 = 0xedb8_8320;
+
+    let crc = bytes.into_iter().fold(u32::MAX, |mut crc, &byte| {
+        crc ^= u32::from(byte);
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ POLYNOMIAL
+            };
+        }
+
+        crc
+    });
+
+    !crc
+}
+
+/// Renders `simulation` before its first step and after every `every` steps until it finishes
+/// or `limit` steps have been taken, always including the last frame.
+///
+/// The frames are rendered as they are taken from the iterator, so a long simulation can be
+/// written out without keeping all of them.
+pub fn record(
+    simulation: &mut dyn Simulation,
+    every: usize,
+    limit: usize,
+) -> impl Iterator<Item = Frame> + '_ {
+    let every = every.max(1);
+    let mut first = true;
+    let mut finished = false;
+    let mut steps = 0;
+
+    std::iter::from_fn(move || {
+        if std::mem::take(&mut first) {
+            return Some(simulation.render());
+        }
+
+        if finished || steps >= limit {
+            return None;
+        }
+
+        let due = every.min(limit - steps);
+        let taken = aoc_common::visualize::run(simulation, due);
+        steps += taken;
+        finished = taken < due;
+
+        (taken > 0).then(|| simulation.render())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Grid;
+
+    use super::*;
+
+    fn frame(origin: Pos<i64>, cells: &str) -> Frame {
+        let cells = Grid::parse(cells, |_, cell| {
+            Ok(match cell {
+                "#" => Cell::Wall,
+                "o" => Cell::Filled,
+                _ => Cell::Empty,
+            })
+        })
+        .unwrap();
+
+        Frame::new(origin, cells)
+    }
+
+    #[test]
+    fn palette() {
+        let palette = "wall=#ffffff,marker=#00ff7f".parse::<Palette>().unwrap();
+
+        assert_eq!(palette.color(Cell::Wall), [0xff, 0xff, 0xff]);
+        assert_eq!(palette.color(Cell::Marker), [0x00, 0xff, 0x7f]);
+        assert_eq!(
+            palette.color(Cell::Empty),
+            Palette::default().color(Cell::Empty)
+        );
+        assert!("floor=#ffffff".parse::<Palette>().is_err());
+        assert!("wall=ffffff".parse::<Palette>().is_err());
+        assert!("wall=#fffff".parse::<Palette>().is_err());
+    }
+
+    #[test]
+    fn canvas() {
+        // A tower growing upwards, with the second frame one row taller
+        let frames = [
+            self::frame(Pos::new(0, -2), "#.#\n###\n"),
+            self::frame(Pos::new(0, -3), ".o.\n#o#\n###\n"),
+        ];
+        let canvas = Canvas::fit(&frames, 2, Palette::default()).unwrap();
+
+        assert_eq!(canvas.size(), (6, 6));
+        assert_eq!(frames[0].get(Pos::new(1, -1)), Cell::Wall);
+        assert_eq!(frames[0].get(Pos::new(1, -3)), Cell::Void);
+
+        let [void, empty, wall, filled] =
+            [Cell::Void, Cell::Empty, Cell::Wall, Cell::Filled].map(|cell| cell as u8);
+        let pixels = canvas.pixels(&frames[0]);
+
+        assert_eq!(pixels[..6], [void; 6]);
+        assert_eq!(pixels[12..18], [wall, wall, empty, empty, wall, wall]);
+        assert_eq!(canvas.pixels(&frames[1])[14], filled);
+        assert!(Canvas::fit(std::iter::empty::<Frame>(), 1, Palette::default()).is_err());
+    }
+
+    #[test]
+    fn png() {
+        let frame = self::frame(Pos::new(0, 0), "#o\n..\n");
+        let canvas = Canvas::fit(std::slice::from_ref(&frame), 3, Palette::default()).unwrap();
+        let mut png = Vec::new();
+        canvas.write_png(&frame, &mut png).unwrap();
+
+        assert_eq!(
+            png[..8],
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+        );
+        // IHDR: 6x6 pixels, with a CRC of the chunk type and data
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..24], [0, 0, 0, 6, 0, 0, 0, 6]);
+        assert_eq!(png[png.len() - 12..], *b"\0\0\0\0IEND\xae\x42\x60\x82");
+    }
+
+    #[test]
+    fn gif() {
+        let frames = [
+            self::frame(Pos::new(0, 0), "#.\n"),
+            self::frame(Pos::new(0, 0), "#o\n"),
+        ];
+        let canvas = Canvas::fit(&frames, 4, Palette::default()).unwrap();
+        let mut gif = Vec::new();
+        canvas.write_gif(&frames, 5, &mut gif).unwrap();
+
+        assert_eq!(gif[..6], *b"GIF89a");
+        assert_eq!(gif[6..10], [8, 0, 4, 0]);
+        assert_eq!(gif.last(), Some(&b';'));
+    }
+}
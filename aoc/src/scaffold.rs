@@ -0,0 +1,414 @@
+//! Scaffolding for a new day: a skeleton solution, an example input to fill in, and the entry in
+//! the [`days!`](aoc_common::days) registry of a year's crate.
+//!
+//! The skeleton's test on the example is ignored until its answers are filled in, so that a new
+//! day never fails the suite. A table for them is also added to `answers.toml`, for the runner to
+//! report them as `UNKNOWN` until then rather than checking them against nothing.
+//!
+//! Nothing is written unless every step can be, so an existing solution is never overwritten and
+//! a failure never leaves the registry half edited.
+
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+
+/// The skeleton of a solution, with `__DAY__` and `__YEAR__` to fill in.
+const TEMPLATE: &str = r#"use anyhow::Result;
+use aoc_common::Solution;
+
+/// <https://adventofcode.com/__YEAR__/day/__DAY__>
+pub struct Day__DAY__;
+
+impl Solution for Day__DAY__ {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(_input: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(0)
+    }
+
+    fn part2(_input: &Self::Input) -> Result<impl std::fmt::Display> {
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "fill in the example's answers"]
+    fn example() {
+        let input = Day__DAY__::parse(include_str!("../in/example/day__DAY__.txt")).unwrap();
+
+        assert_eq!(Day__DAY__::part1(&input).unwrap().to_string(), "");
+        assert_eq!(Day__DAY__::part2(&input).unwrap().to_string(), "");
+    }
+}
+"#;
+
+/// Creates the solution and example input for `day` under the year's crate at `root`, and
+/// registers the day, returning the files written.
+///
+/// A table for the example's answers is added to `answers.toml`, to fill in.
+///
+/// # Errors
+///
+/// Returns an error if the solution or example input already exists, the day is already
+/// registered, or the files cannot be written.
+pub fn create(root: &Path, day: u8, year: u16) -> Result<Vec<PathBuf>> {
+    let solution = root.join("src").join(format!("day{day}.rs"));
+    let example = root
+        .join("in")
+        .join("example")
+        .join(format!("day{day}.txt"));
+    let lib = root.join("src").join("lib.rs");
+    let answers = root.join("answers.toml");
+
+    for path in [&solution, &example] {
+        if path.exists() {
+            bail!("'{}' already exists", path.display());
+        }
+    }
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).with_context(|| format!("failed to read '{}'", path.display()))
+    };
+    let registry = read(&lib)?;
+    let registered = self::register(&registry, day)?;
+    let mut edits = vec![(&lib, registry, registered)];
+
+    // A missing answers file has no answers yet
+    let old = if answers.exists() {
+        read(&answers)?
+    } else {
+        String::new()
+    };
+    let new = self::expect(&old, day)?;
+    edits.push((&answers, old, new));
+
+    let source = TEMPLATE
+        .replace("__DAY__", &day.to_string())
+        .replace("__YEAR__", &year.to_string());
+
+    let mut created = Vec::new();
+    let mut edited = Vec::new();
+    let result = (|| {
+        for (path, contents) in [(&solution, source.as_str()), (&example, "")] {
+            self::create_new(path, contents)?;
+            created.push(path.clone());
+        }
+
+        for (path, _, new) in &edits {
+            self::replace(path, new)?;
+            edited.push(*path);
+        }
+
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        for path in &created {
+            let _ = fs::remove_file(path);
+        }
+
+        for (path, old, _) in &edits[..edited.len()] {
+            let _ = self::replace(path, old);
+        }
+
+        return Err(err);
+    }
+
+    created.extend(edited.into_iter().cloned());
+    Ok(created)
+}
+
+/// Replaces the contents of a file whole, so that it is never left half written.
+fn replace(path: &Path, contents: &str) -> Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+
+    fs::write(&partial, contents)
+        .and_then(|()| fs::rename(&partial, path))
+        .with_context(|| format!("failed to write '{}'", path.display()))
+}
+
+/// Writes a file that must not exist yet, creating its directory if needed.
+fn create_new(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create '{}'", dir.display()))?;
+    }
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .with_context(|| format!("failed to create '{}'", path.display()))
+}
+
+/// Adds `day` to the `days!` registry in the source of `lib.rs` and declares its module, keeping
+/// the days in order.
+fn register(lib: &str, day: u8) -> Result<String> {
+    let no_registry = || anyhow!("no `days!` registry in lib.rs");
+
+    let start = lib.find("\ndays! {\n").ok_or_else(no_registry)? + "\ndays! {\n".len();
+    // Either empty, as in a new year's crate, or up to the end of the last entry
+    let len = if lib[start..].starts_with('}') {
+        0
+    } else {
+        lib[start..].find("\n}").ok_or_else(no_registry)? + 1
+    };
+
+    let mut offset = start;
+    let mut insert = start + len;
+
+    for entry in lib[start..start + len].lines() {
+        let registered = entry
+            .split_once(" =>")
+            .and_then(|(n, _)| n.trim().parse::<u8>().ok())
+            .ok_or_else(|| anyhow!("unexpected registry entry '{}'", entry.trim()))?;
+
+        if registered == day {
+            bail!("day {day} is already registered");
+        }
+
+        if registered > day {
+            insert = offset;
+            break;
+        }
+
+        offset += entry.len() + 1;
+    }
+
+    let mut lib = lib.to_string();
+    lib.insert_str(insert, &format!("    {day} => day{day}::Day{day},\n"));
+
+    self::declare(&lib, day)
+}
+
+/// Declares the module of `day` among those of the other days, or just before the registry if
+/// there are none yet.
+fn declare(lib: &str, day: u8) -> Result<String> {
+    // Before the first module of a later day, or else after the last of an earlier one
+    let mut later = None;
+    let mut earlier = None;
+    let mut offset = 0;
+
+    for line in lib.split_inclusive('\n') {
+        let declared = line
+            .strip_prefix("pub mod day")
+            .and_then(|rest| rest.trim_end().strip_suffix(';'))
+            .and_then(|n| n.parse::<u8>().ok());
+
+        match declared {
+            Some(declared) if declared == day => bail!("module day{day} is already declared"),
+            Some(declared) if declared > day => {
+                later.get_or_insert(offset);
+            }
+            Some(_) => earlier = Some(offset + line.len()),
+            None => {}
+        }
+
+        offset += line.len();
+    }
+
+    let mut lib = lib.to_string();
+
+    if let Some(insert) = later.or(earlier) {
+        lib.insert_str(insert, &format!("pub mod day{day};\n"));
+    } else {
+        let registry = lib
+            .find("\ndays! {\n")
+            .ok_or_else(|| anyhow!("no `days!` registry in lib.rs"))?;
+        lib.insert_str(registry + 1, &format!("pub mod day{day};\n\n"));
+    }
+
+    Ok(lib)
+}
+
+/// Adds a table for the answers to the example of `day` to the source of `answers.toml`, keeping
+/// the days in order. The answers are left commented out, to be filled in from the puzzle.
+fn expect(answers: &str, day: u8) -> Result<String> {
+    let table = format!("[day{day}.example]");
+    let stub = format!("{table}\n# part1 =\n# part2 =\n");
+    let mut offset = 0;
+
+    for line in answers.split_inclusive('\n') {
+        if line.trim_end() == table {
+            bail!("'answers.toml' already has answers to the example of day {day}");
+        }
+
+        let later = line
+            .strip_prefix("[day")
+            .and_then(|rest| rest.split_once('.'))
+            .and_then(|(n, _)| n.parse::<u8>().ok())
+            .is_some_and(|n| n > day);
+
+        if later {
+            let mut answers = answers.to_string();
+            answers.insert_str(offset, &format!("{stub}\n"));
+
+            return Ok(answers);
+        }
+
+        offset += line.len();
+    }
+
+    let mut answers = answers.trim_end().to_string();
+
+    if !answers.is_empty() {
+        answers.push_str("\n\n");
+    }
+
+    answers.push_str(&stub);
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "\
+use anyhow::Result;
+
+pub mod day1;
+pub mod day3;
+
+days! {
+    1 => day1::Day1,
+    3 => day3::Day3,
+}
+
+pub trait Solution {}
+";
+
+    const ANSWERS: &str = "\
+# Expected answers
+
+[day1.example]
+part1 = 24_000
+
+[day3.default]
+part1 = 157
+";
+
+    #[test]
+    fn register() {
+        let registered = |day| super::register(LIB, day).unwrap();
+
+        assert!(registered(2).contains("    1 => day1::Day1,\n    2 => day2::Day2,\n    3 =>"));
+        assert!(registered(2).contains("pub mod day1;\npub mod day2;\npub mod day3;\n\ndays!"));
+        assert!(registered(25).contains("    3 => day3::Day3,\n    25 => day25::Day25,\n}\n"));
+        assert!(registered(25).contains("pub mod day3;\npub mod day25;\n\ndays!"));
+        assert_eq!(
+            super::register(LIB, 3).unwrap_err().to_string(),
+            "day 3 is already registered"
+        );
+        assert!(super::register("pub mod day1;\n", 2).is_err());
+        assert_eq!(
+            super::register("use aoc_common::days;\n\ndays! {\n}\n", 1).unwrap(),
+            "use aoc_common::days;\n\npub mod day1;\n\ndays! {\n    1 => day1::Day1,\n}\n"
+        );
+        assert!(
+            super::register("pub mod day2;\n\ndays! {\n}\n", 2)
+                .unwrap_err()
+                .to_string()
+                .contains("already declared")
+        );
+    }
+
+    #[test]
+    fn expect() {
+        let expected = |day| super::expect(ANSWERS, day).unwrap();
+
+        assert!(expected(2).contains(
+            "part1 = 24_000\n\n[day2.example]\n# part1 =\n# part2 =\n\n[day3.default]\n"
+        ));
+        assert!(expected(4).ends_with("part1 = 157\n\n[day4.example]\n# part1 =\n# part2 =\n"));
+        assert!(super::expect(ANSWERS, 1).is_err());
+        assert_eq!(
+            super::expect("", 1).unwrap(),
+            "[day1.example]\n# part1 =\n# part2 =\n"
+        );
+    }
+
+    #[test]
+    fn create() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+
+        let created = super::create(&root, 2, 2022).unwrap();
+
+        assert_eq!(
+            created,
+            [
+                "src/day2.rs",
+                "in/example/day2.txt",
+                "src/lib.rs",
+                "answers.toml",
+            ]
+            .map(|path| root.join(path))
+        );
+        assert!(
+            fs::read_to_string(root.join("src/lib.rs"))
+                .unwrap()
+                .contains("pub mod day2;\npub mod day3;\n\ndays! {\n    1 => day1::Day1,\n    2 => day2::Day2,\n")
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("in/example/day2.txt")).unwrap(),
+            ""
+        );
+        // Reported as unknown until its answers are filled in
+        assert_eq!(
+            fs::read_to_string(root.join("answers.toml")).unwrap(),
+            "[day2.example]\n# part1 =\n# part2 =\n"
+        );
+
+        // Refuses to overwrite, and leaves the registry alone
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        fs::remove_file(root.join("src/day2.rs")).unwrap();
+
+        assert!(super::create(&root, 2, 2022).is_err());
+        assert!(!root.join("src/day2.rs").exists());
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+
+        // Writes nothing when the answers cannot be added either
+        fs::remove_file(root.join("in/example/day2.txt")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        let answers = fs::read_to_string(root.join("answers.toml")).unwrap();
+
+        assert!(super::create(&root, 2, 2022).is_err());
+        assert!(!root.join("src/day2.rs").exists());
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), LIB);
+        assert_eq!(
+            fs::read_to_string(root.join("answers.toml")).unwrap(),
+            answers
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn template() {
+        let source = TEMPLATE.replace("__DAY__", "7").replace("__YEAR__", "2022");
+
+        assert!(!source.contains("__"));
+        assert!(source.contains("adventofcode.com/2022/day/7>"));
+        // Ignored until the example's answers are filled in
+        assert!(source.contains("#[ignore = "));
+        assert!(source.contains("include_str!(\"../in/example/day7.txt\")"));
+    }
+}
@@ -0,0 +1,222 @@
+//! Terminal playback of the puzzle simulations, one frame per step.
+//!
+//! Frames are the simulation's `Display` output, drawn with ANSI escape codes on the alternate
+//! screen. Playback can be paused, stepped through and sped up or slowed down, and frames larger
+//! than the terminal can be panned around:
+//!
+//! | Key                   | Action                                 |
+//! |-----------------------|----------------------------------------|
+//! | `space`               | Pause or resume                        |
+//! | `n` or `.`            | Pause, then advance a single step      |
+//! | `+` / `-`             | Double or halve the speed              |
+//! | arrows or `h j k l`   | Pan around the frame                   |
+//! | `0`                   | Pan back to the top left               |
+//! | `q`, `esc` or `ctrl-c`| Quit                                   |
+
+use std::io::IsTerminal;
+use std::io::Write;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use anyhow::bail;
+use aoc_common::visualize;
+use aoc_common::visualize::Simulation;
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::queue;
+use crossterm::style;
+use crossterm::terminal;
+
+/// Playback state of [`play`].
+#[derive(Clone, Copy, Debug)]
+struct Playback {
+    /// Steps per second.
+    speed: u32,
+    paused: bool,
+    finished: bool,
+    steps: usize,
+    /// Top left corner of the frame shown, as `(column, line)`.
+    view: (usize, usize),
+}
+
+/// Switches the terminal to raw mode on the alternate screen, restoring it when dropped.
+struct Screen;
+
+impl Screen {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+
+        let screen = Self;
+        let mut stdout = std::io::stdout();
+        queue!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+        stdout.flush()?;
+
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let mut stdout = std::io::stdout();
+        let _ = queue!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = stdout.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl Playback {
+    const SPEED_MAX: u32 = 1 << 20;
+
+    /// Applies a key press, returning `false` to quit.
+    fn press(&mut self, key: KeyEvent, simulation: &mut dyn Simulation) -> bool {
+        const PAN: usize = 4;
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('n' | '.') => {
+                self.paused = true;
+                self.advance(simulation, 1);
+            }
+            KeyCode::Char('+' | '=') => self.speed = (self.speed * 2).min(Self::SPEED_MAX),
+            KeyCode::Char('-' | '_') => self.speed = (self.speed / 2).max(1),
+            KeyCode::Left | KeyCode::Char('h') => self.view.0 = self.view.0.saturating_sub(PAN),
+            KeyCode::Right | KeyCode::Char('l') => self.view.0 += PAN,
+            KeyCode::Up | KeyCode::Char('k') => self.view.1 = self.view.1.saturating_sub(PAN),
+            KeyCode::Down | KeyCode::Char('j') => self.view.1 += PAN,
+            KeyCode::Char('0') | KeyCode::Home => self.view = (0, 0),
+            _ => {}
+        }
+
+        true
+    }
+
+    fn advance(&mut self, simulation: &mut dyn Simulation, steps: usize) {
+        if !self.finished {
+            let taken = visualize::run(simulation, steps);
+            self.steps += taken;
+            self.finished = taken < steps;
+        }
+    }
+
+    fn status(&self) -> String {
+        let state = if self.finished {
+            "finished"
+        } else if self.paused {
+            "paused"
+        } else {
+            "playing"
+        };
+
+        format!(
+            " step {} | {} steps/s | {state} | space pause, n step, +/- speed, arrows pan, q quit",
+            self.steps, self.speed
+        )
+    }
+
+    /// Draws the part of `frame` in view, with the status line below it.
+    fn draw(&self, frame: &str) -> Result<()> {
+        let (cols, rows) = terminal::size()?;
+        let (cols, rows) = (usize::from(cols), usize::from(rows));
+        let mut stdout = std::io::stdout().lock();
+
+        queue!(stdout, cursor::MoveTo(0, 0))?;
+
+        for line in frame.lines().skip(self.view.1).take(rows.saturating_sub(1)) {
+            let line = line
+                .chars()
+                .skip(self.view.0)
+                .take(cols)
+                .collect::<String>();
+            queue!(
+                stdout,
+                style::Print(line),
+                terminal::Clear(terminal::ClearType::UntilNewLine),
+                style::Print("\r\n"),
+            )?;
+        }
+
+        let status = self.status().chars().take(cols).collect::<String>();
+        let row = u16::try_from(rows.saturating_sub(1)).unwrap_or(u16::MAX);
+
+        queue!(
+            stdout,
+            terminal::Clear(terminal::ClearType::FromCursorDown),
+            cursor::MoveTo(0, row),
+            style::SetAttribute(style::Attribute::Reverse),
+            style::Print(format!("{status:cols$}")),
+            style::SetAttribute(style::Attribute::Reset),
+        )?;
+        stdout.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Plays `simulation` in the terminal, starting at `speed` steps per second, until the user
+/// quits.
+///
+/// # Errors
+///
+/// Returns an error if standard output is not a terminal, or the terminal cannot be controlled.
+pub fn play(simulation: &mut dyn Simulation, speed: u32) -> Result<()> {
+    const FRAME: Duration = Duration::from_millis(1_000 / 30);
+
+    if !std::io::stdout().is_terminal() {
+        bail!("the visualizer needs a terminal");
+    }
+
+    let _screen = Screen::enter()?;
+    let mut playback = Playback {
+        speed: speed.clamp(1, Playback::SPEED_MAX),
+        paused: false,
+        finished: false,
+        steps: 0,
+        view: (0, 0),
+    };
+    // Fractional steps carried over between frames at low speeds
+    let mut due = 0.0;
+    let mut last = Instant::now();
+
+    loop {
+        playback.draw(&simulation.to_string())?;
+
+        let deadline = Instant::now() + FRAME;
+
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            if !event::poll(timeout)? {
+                break;
+            }
+
+            if let Event::Key(key) = event::read()?
+                && key.kind != KeyEventKind::Release
+                && !playback.press(key, simulation)
+            {
+                return Ok(());
+            }
+        }
+
+        let now = Instant::now();
+
+        if playback.paused || playback.finished {
+            due = 0.0;
+        } else {
+            due += now.duration_since(last).as_secs_f64() * f64::from(playback.speed);
+
+            let steps = due.floor();
+            due -= steps;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            playback.advance(simulation, steps as usize);
+        }
+
+        last = now;
+    }
+}
@@ -0,0 +1,197 @@
+use std::fs;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use aoc::fetch;
+use aoc::fetch::Client;
+use aoc::fetch::Fetched;
+
+const SESSION: &str = "53616c7465645f5f";
+
+/// A request received by the mock server: its request line and `Cookie` and `User-Agent`
+/// headers.
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    line: String,
+    cookie: Option<String>,
+    user_agent: Option<String>,
+}
+
+/// Serves the input `day N` for each day, as long as the session cookie matches, and sends the
+/// requests it receives down the returned channel. Day 25 is refused for too many requests.
+fn serve() -> (String, mpsc::Receiver<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut lines = BufReader::new(&stream).lines().map(Result::unwrap);
+            let line = lines.next().unwrap();
+            let headers = lines
+                .take_while(|header| !header.is_empty())
+                .collect::<Vec<_>>();
+            let header = |name: &str| {
+                headers
+                    .iter()
+                    .find_map(|header| header.strip_prefix(name).map(str::to_string))
+            };
+            let cookie = header("cookie: ");
+            let user_agent = header("user-agent: ");
+
+            let day = line
+                .strip_prefix("GET /2022/day/")
+                .and_then(|path| path.strip_suffix("/input HTTP/1.1"));
+            let (status, body) = match day {
+                _ if cookie.as_deref() != Some(&format!("session={SESSION}")) => {
+                    ("400 Bad Request", "Please log in".to_string())
+                }
+                Some("25") => ("429 Too Many Requests\r\nRetry-After: 60", String::new()),
+                Some(day) => ("200 OK", format!("day {day}\n")),
+                None => ("404 Not Found", "Not found".to_string()),
+            };
+
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            if tx
+                .send(Request {
+                    line,
+                    cookie,
+                    user_agent,
+                })
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    (base_url, rx)
+}
+
+fn dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    dir
+}
+
+#[test]
+fn fetch() {
+    let (base_url, requests) = self::serve();
+    let dir = self::dir("fetch");
+    let mut client = Client::new(&base_url, SESSION).interval(Duration::ZERO);
+
+    let path = dir.join("day3.txt");
+    assert_eq!(
+        fetch::fetch(&mut client, &dir, 2022, 3).unwrap(),
+        Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "day 3\n");
+    assert_eq!(
+        requests.recv().unwrap(),
+        Request {
+            line: "GET /2022/day/3/input HTTP/1.1".to_string(),
+            cookie: Some(format!("session={SESSION}")),
+            user_agent: Some(Client::USER_AGENT.to_string()),
+        }
+    );
+
+    // Cached from now on, without another request
+    assert_eq!(
+        fetch::fetch(&mut client, &dir, 2022, 3).unwrap(),
+        Fetched::Cached(path)
+    );
+    assert!(requests.try_recv().is_err());
+
+    // An empty file, as left by the scaffold, is fetched again
+    fs::write(dir.join("day4.txt"), "").unwrap();
+    fetch::fetch(&mut client, &dir, 2022, 4).unwrap();
+    assert_eq!(fs::read_to_string(dir.join("day4.txt")).unwrap(), "day 4\n");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rejected() {
+    let (base_url, _requests) = self::serve();
+    let dir = self::dir("rejected");
+    let mut client = Client::new(&base_url, "expired").interval(Duration::ZERO);
+
+    let err = fetch::fetch(&mut client, &dir, 2022, 5).unwrap_err();
+
+    assert!(
+        err.to_string().contains("session token was rejected"),
+        "{err}"
+    );
+    assert!(!dir.join("day5.txt").exists());
+}
+
+#[test]
+fn no_such_day() {
+    let (base_url, requests) = self::serve();
+    let mut client = Client::new(&base_url, SESSION).interval(Duration::ZERO);
+
+    assert!(client.input(2022, 26).is_err());
+    assert!(client.input(2022, 0).is_err());
+    assert!(client.input(2014, 1).is_err());
+    assert!(requests.try_recv().is_err());
+}
+
+#[test]
+fn interval() {
+    const INTERVAL: Duration = Duration::from_millis(200);
+
+    let (base_url, _requests) = self::serve();
+    let mut client = Client::new(&base_url, SESSION).interval(INTERVAL);
+    let start = std::time::Instant::now();
+
+    client.input(2022, 1).unwrap();
+    client.input(2022, 2).unwrap();
+
+    assert!(start.elapsed() >= INTERVAL);
+}
+
+#[test]
+fn user_agent() {
+    let (base_url, requests) = self::serve();
+    let mut client = Client::new(&base_url, SESSION)
+        .interval(Duration::ZERO)
+        .user_agent("aoc fetch (elf@example.com)");
+
+    client.input(2022, 1).unwrap();
+
+    assert_eq!(
+        requests.recv().unwrap().user_agent.as_deref(),
+        Some("aoc fetch (elf@example.com)")
+    );
+}
+
+#[test]
+fn too_many_requests() {
+    let (base_url, _requests) = self::serve();
+    let dir = self::dir("too-many-requests");
+    let mut client = Client::new(&base_url, SESSION).interval(Duration::ZERO);
+
+    let err = fetch::fetch(&mut client, &dir, 2022, 25).unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("too many requests to http://127.0.0.1:"),
+        "{err}"
+    );
+    assert!(err.to_string().ends_with("try again in 60s"), "{err}");
+    assert!(!dir.join("day25.txt").exists());
+}
@@ -0,0 +1,253 @@
+//! Cycle detection for simulations whose state eventually repeats.
+//!
+//! A simulation is stepped until the key of its state matches an earlier one, after which a
+//! metric of the state (e.g. the height of a tower) is assumed to grow by the same delta with
+//! every cycle, so it can be extrapolated to step counts far too large to simulate.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::convert::Infallible;
+use std::hash::Hash;
+
+/// A cycle found by [`find`], along with the metric after every step up to its first repeat.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<M> {
+    start: usize,
+    period: usize,
+    metrics: Vec<M>,
+}
+
+impl<M> Cycle<M> {
+    /// The number of steps before the state first enters the cycle.
+    #[must_use]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The length of the cycle, i.e. the number of steps after which the state repeats.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// The metric before the first step and after every step up to the first repeat, for
+    /// metrics that say what happened during a step rather than add up.
+    #[must_use]
+    pub fn metrics(&self) -> &[M] {
+        &self.metrics
+    }
+}
+
+impl<M: Metric> Cycle<M> {
+    /// How much the metric changes over one cycle, or `None` if that does not fit in `M`.
+    #[must_use]
+    pub fn delta(&self) -> Option<M> {
+        self.metrics[self.start + self.period].checked_sub(self.metrics[self.start])
+    }
+
+    /// The metric after `steps` steps, or `None` if it or the number of cycles does not fit in
+    /// `M`.
+    #[must_use]
+    pub fn extrapolate(&self, steps: usize) -> Option<M> {
+        if let Some(&metric) = self.metrics.get(steps) {
+            return Some(metric);
+        }
+
+        let cycles = M::try_from((steps - self.start) / self.period).ok()?;
+        let rest = (steps - self.start) % self.period;
+
+        self.delta()?
+            .checked_mul(cycles)?
+            .checked_add(self.metrics[self.start + rest])
+    }
+}
+
+/// A metric that can be extrapolated over many cycles, with arithmetic that fails rather than
+/// overflows.
+pub trait Metric: Copy + TryFrom<usize> {
+    #[must_use]
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    #[must_use]
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    #[must_use]
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_metric {
+    ($($t:ty),*) => {
+        $(
+            impl Metric for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_metric!(i8, i16, i32, i64, i128, isize);
+impl_metric!(u8, u16, u32, u64, u128, usize);
+
+/// Steps `state` until its `key` repeats, recording `metric` before the first and after every
+/// step.
+///
+/// Returns `None` if there was no cycle within `limit` steps, leaving `state` stepped `limit`
+/// times; otherwise `state` is left at the first repeat.
+pub fn find<S, K, M>(
+    state: &mut S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+    metric: impl FnMut(&S) -> M,
+) -> Option<Cycle<M>>
+where
+    K: Eq + Hash,
+{
+    let Ok(cycle) = self::try_find(
+        state,
+        limit,
+        |state| {
+            step(state);
+            Ok::<_, Infallible>(())
+        },
+        key,
+        metric,
+    );
+
+    cycle
+}
+
+/// Like [`find`], for a `step` that can fail.
+///
+/// # Errors
+///
+/// Returns the first error of `step`, leaving `state` as it failed.
+pub fn try_find<S, K, M, E>(
+    state: &mut S,
+    limit: usize,
+    mut step: impl FnMut(&mut S) -> Result<(), E>,
+    mut key: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> M,
+) -> Result<Option<Cycle<M>>, E>
+where
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::from([(key(state), 0)]);
+    let mut metrics = vec![metric(state)];
+
+    for steps in 1..=limit {
+        step(state)?;
+        metrics.push(metric(state));
+
+        match seen.entry(key(state)) {
+            Entry::Vacant(entry) => {
+                entry.insert(steps);
+            }
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+
+                return Ok(Some(Cycle {
+                    start,
+                    period: steps - start,
+                    metrics,
+                }));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up from 0 and then around `2, 3, 4`, summing the numbers counted.
+    fn step((n, sum): &mut (u64, u64)) {
+        *n = if *n == 4 { 2 } else { *n + 1 };
+        *sum += *n;
+    }
+
+    fn cycle(limit: usize) -> Option<Cycle<u64>> {
+        super::find(&mut (0, 0), limit, self::step, |&(n, _)| n, |&(_, sum)| sum)
+    }
+
+    #[test]
+    fn find() {
+        let cycle = self::cycle(100).unwrap();
+
+        assert_eq!((cycle.start(), cycle.period()), (2, 3));
+        assert_eq!(cycle.delta(), Some(2 + 3 + 4));
+        assert_eq!(cycle.metrics(), [0, 1, 3, 6, 10, 12]);
+        assert_eq!(self::cycle(4), None);
+    }
+
+    #[test]
+    fn try_find() {
+        // Fails on reaching 4, before it could repeat
+        let mut state = (0, 0);
+        let err = super::try_find(
+            &mut state,
+            100,
+            |state| {
+                self::step(state);
+                if state.0 == 4 { Err(state.1) } else { Ok(()) }
+            },
+            |&(n, _)| n,
+            |&(_, sum)| sum,
+        );
+
+        assert_eq!(err, Err(10));
+        assert_eq!(state, (4, 10));
+    }
+
+    #[test]
+    fn extrapolate() {
+        let cycle = self::cycle(100).unwrap();
+        let mut state = (0, 0);
+
+        for steps in 0..20 {
+            assert_eq!(cycle.extrapolate(steps), Some(state.1), "{steps} steps");
+            self::step(&mut state);
+        }
+
+        // 1 + 2 for the first two steps, then 9 for every full cycle and 3 + 4 for the rest
+        assert_eq!(
+            cycle.extrapolate(1_000_000_000_000),
+            Some(3 + 9 * 333_333_333_332 + 3 + 4)
+        );
+        assert_eq!(
+            super::find(&mut 0_u8, 10, |n| *n ^= 1, |&n| n, |&n| n)
+                .unwrap()
+                .extrapolate(usize::MAX),
+            None
+        );
+
+        // Counts up by 2 with every step, so overflows after half of `u64::MAX` steps
+        let cycle = super::find(
+            &mut (false, 0_u64),
+            10,
+            |(flip, sum)| {
+                *flip = !*flip;
+                *sum += 2;
+            },
+            |&(flip, _)| flip,
+            |&(_, sum)| sum,
+        )
+        .unwrap();
+        let half = usize::try_from(u64::MAX / 2).unwrap();
+
+        assert_eq!(cycle.extrapolate(half), Some(u64::MAX - 1));
+        assert_eq!(cycle.extrapolate(half + 1), None);
+    }
+}
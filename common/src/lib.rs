@@ -0,0 +1,1148 @@
+//! What every year's solutions share: the [`Solution`] trait and the [`days!`] registry, the
+//! geometry of [`Pos`] and [`Grid`], and the parsing, search and simulation helpers built on them.
+
+use std::marker::PhantomData;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::bail;
+
+use crate::parse::ParseError;
+use crate::visualize::Simulation;
+
+pub mod cycle;
+pub mod ocr;
+pub mod parse;
+pub mod render;
+pub mod search;
+pub mod visualize;
+
+/// Registers a year's days, listing them all in `DAYS`. Each day's module is declared as usual,
+/// so that tools like rustfmt find it:
+///
+/// ```ignore
+/// pub mod day1;
+/// pub mod day2;
+///
+/// days! {
+///     1 => day1::Day1,
+///     2 => day2::Day2,
+/// }
+/// ```
+#[macro_export]
+macro_rules! days {
+    ($($day:literal => $module:ident::$solution:ident),* $(,)?) => {
+        /// Every registered day, in ascending order.
+        pub const DAYS: &[$crate::Day] = &[$($crate::Day::new::<$module::$solution>($day)),*];
+    };
+}
+
+/// A day's puzzle: how to parse its input and solve both of its parts.
+pub trait Solution {
+    type Input;
+
+    /// Parses the raw puzzle input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// Solves the first part of the puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the puzzle has no solution for the given input.
+    fn part1(input: &Self::Input) -> Result<impl std::fmt::Display>;
+
+    /// Solves the second part of the puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the puzzle has no solution for the given input.
+    fn part2(input: &Self::Input) -> Result<impl std::fmt::Display>;
+
+    /// Starts a step-by-step simulation of the given part being solved, for the days that have
+    /// one to show.
+    ///
+    /// Returns an error if the simulation cannot be set up for the given input.
+    fn simulate(input: &Self::Input, part: Part) -> Option<Result<Box<dyn Simulation + '_>>> {
+        let _ = (input, part);
+        None
+    }
+
+    /// Traces how the input is executed, one line per step, for the days whose input is a
+    /// program to run.
+    fn trace(input: &Self::Input) -> Option<Box<dyn Iterator<Item = String> + '_>> {
+        let _ = input;
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// A parsed input whose [`Solution`] has been erased.
+pub trait Parsed {
+    /// Solves the given part of the puzzle, rendering the answer as a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the puzzle has no solution for the parsed input.
+    fn solve(&self, part: Part) -> Result<String>;
+
+    /// Starts a simulation of the given part, if the day has one.
+    ///
+    /// Returns an error if the simulation cannot be set up for the parsed input.
+    fn simulate(&self, part: Part) -> Option<Result<Box<dyn Simulation + '_>>>;
+
+    /// Traces the execution of the parsed input, if the day has one.
+    fn trace(&self) -> Option<Box<dyn Iterator<Item = String> + '_>>;
+}
+
+/// A registered day, so that all days can be handled uniformly.
+#[derive(Clone, Copy, Debug)]
+pub struct Day {
+    day: u8,
+    parse: fn(&str) -> Result<Box<dyn Parsed>>,
+}
+
+/// A year's solvers, as registered in its crate with [`days!`].
+#[derive(Clone, Copy, Debug)]
+pub struct Year {
+    year: u16,
+    days: &'static [Day],
+}
+
+struct Input<S: Solution> {
+    input: S::Input,
+    solution: PhantomData<S>,
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(part: &str) -> Result<Self> {
+        match part {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            _ => bail!("invalid part '{part}'"),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl<S: Solution> Parsed for Input<S> {
+    fn solve(&self, part: Part) -> Result<String> {
+        Ok(match part {
+            Part::One => S::part1(&self.input)?.to_string(),
+            Part::Two => S::part2(&self.input)?.to_string(),
+        })
+    }
+
+    fn simulate(&self, part: Part) -> Option<Result<Box<dyn Simulation + '_>>> {
+        S::simulate(&self.input, part)
+    }
+
+    fn trace(&self) -> Option<Box<dyn Iterator<Item = String> + '_>> {
+        S::trace(&self.input)
+    }
+}
+
+impl Part {
+    pub const ALL: [Self; 2] = [Self::One, Self::Two];
+}
+
+impl Day {
+    /// Registers the [`Solution`] for `day`, as done by [`days!`].
+    #[must_use]
+    pub const fn new<S: Solution + 'static>(day: u8) -> Self {
+        fn parse<S: Solution + 'static>(input: &str) -> Result<Box<dyn Parsed>> {
+            Ok(Box::new(Input::<S> {
+                input: S::parse(input)?,
+                solution: PhantomData,
+            }))
+        }
+
+        Self {
+            day,
+            parse: parse::<S>,
+        }
+    }
+
+    #[must_use]
+    pub const fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Parses the raw puzzle input with this day's [`Solution`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    pub fn parse(self, input: &str) -> Result<Box<dyn Parsed>> {
+        (self.parse)(input).map_err(|err| match err.downcast::<ParseError>() {
+            Ok(err) => err.locate(self.day, input).into(),
+            Err(err) => err,
+        })
+    }
+}
+
+impl Year {
+    #[must_use]
+    pub const fn new(year: u16, days: &'static [Day]) -> Self {
+        Self { year, days }
+    }
+
+    #[must_use]
+    pub const fn year(self) -> u16 {
+        self.year
+    }
+
+    /// Every registered day, in ascending order.
+    #[must_use]
+    pub const fn days(self) -> &'static [Day] {
+        self.days
+    }
+
+    /// Looks up the registered day.
+    #[must_use]
+    pub fn get(self, day: u8) -> Option<Day> {
+        self.days.iter().copied().find(|d| d.day == day)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pos<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Pos<T>
+where
+    T: Copy,
+{
+    #[must_use]
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub const fn x(self) -> T {
+        self.x
+    }
+
+    #[must_use]
+    pub const fn y(self) -> T {
+        self.y
+    }
+}
+
+impl<T> Add<Self> for Pos<T>
+where
+    T: Add<T, Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T> AddAssign<Self> for Pos<T>
+where
+    T: AddAssign<T>,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T> Sub<Self> for Pos<T>
+where
+    T: Sub<T, Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T> SubAssign<Self> for Pos<T>
+where
+    T: SubAssign<T>,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> Mul<T> for Pos<T>
+where
+    T: Copy + Mul<T, Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T> MulAssign<T> for Pos<T>
+where
+    T: Copy + MulAssign<T>,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+macro_rules! impl_pos_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Pos<$t> {
+                #[must_use]
+                pub const fn up(self) -> Self {
+                    Self { y: self.y - 1, ..self }
+                }
+
+                #[must_use]
+                pub const fn right(self) -> Self {
+                    Self { x: self.x + 1, ..self }
+                }
+
+                #[must_use]
+                pub const fn down(self) -> Self {
+                    Self { y: self.y + 1, ..self }
+                }
+
+                #[must_use]
+                pub const fn left(self) -> Self {
+                    Self { x: self.x - 1, ..self }
+                }
+
+                #[must_use]
+                pub const fn up_right(self) -> Self {
+                    Self { x: self.x + 1, y: self.y - 1 }
+                }
+
+                #[must_use]
+                pub const fn down_right(self) -> Self {
+                    Self { x: self.x + 1, y: self.y + 1 }
+                }
+
+                #[must_use]
+                pub const fn down_left(self) -> Self {
+                    Self { x: self.x - 1, y: self.y + 1 }
+                }
+
+                #[must_use]
+                pub const fn up_left(self) -> Self {
+                    Self { x: self.x - 1, y: self.y - 1 }
+                }
+
+                pub fn adj(self) -> impl Iterator<Item = Self> {
+                    [self.up(), self.right(), self.down(), self.left()].into_iter()
+                }
+
+                /// The eight positions around `self`, clockwise from `up`.
+                pub fn adj8(self) -> impl Iterator<Item = Self> {
+                    [
+                        self.up(),
+                        self.up_right(),
+                        self.right(),
+                        self.down_right(),
+                        self.down(),
+                        self.down_left(),
+                        self.left(),
+                        self.up_left(),
+                    ]
+                    .into_iter()
+                }
+
+                /// The taxicab distance to `other`, moving only up, right, down or left.
+                #[must_use]
+                pub const fn manhattan(self, other: Self) -> $t {
+                    (self.x - other.x).abs() + (self.y - other.y).abs()
+                }
+
+                /// The distance to `other` when diagonal steps are allowed too.
+                #[must_use]
+                pub fn chebyshev(self, other: Self) -> $t {
+                    (self.x - other.x).abs().max((self.y - other.y).abs())
+                }
+
+                /// Each coordinate's sign, i.e. the single step (diagonal or not) towards
+                /// `self` from the origin.
+                #[must_use]
+                pub const fn signum(self) -> Self {
+                    Self { x: self.x.signum(), y: self.y.signum() }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_pos_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Pos<$t> {
+                #[must_use]
+                pub fn up(self) -> Option<Self> {
+                    Some(Self { y: self.y.checked_sub(1)?, ..self })
+                }
+
+                #[must_use]
+                pub fn right(self) -> Option<Self> {
+                    Some(Self { x: self.x.checked_add(1)?, ..self })
+                }
+
+                #[must_use]
+                pub fn down(self) -> Option<Self> {
+                    Some(Self { y: self.y.checked_add(1)?, ..self })
+                }
+
+                #[must_use]
+                pub fn left(self) -> Option<Self> {
+                    Some(Self { x: self.x.checked_sub(1)?, ..self })
+                }
+
+                #[must_use]
+                pub fn up_right(self) -> Option<Self> {
+                    self.up()?.right()
+                }
+
+                #[must_use]
+                pub fn down_right(self) -> Option<Self> {
+                    self.down()?.right()
+                }
+
+                #[must_use]
+                pub fn down_left(self) -> Option<Self> {
+                    self.down()?.left()
+                }
+
+                #[must_use]
+                pub fn up_left(self) -> Option<Self> {
+                    self.up()?.left()
+                }
+
+                pub fn adj(self) -> impl Iterator<Item = Self> {
+                    [self.up(), self.right(), self.down(), self.left()]
+                        .into_iter()
+                        .flatten()
+                }
+
+                /// The eight positions around `self`, clockwise from `up`, skipping any that
+                /// would overflow.
+                pub fn adj8(self) -> impl Iterator<Item = Self> {
+                    [
+                        self.up(),
+                        self.up_right(),
+                        self.right(),
+                        self.down_right(),
+                        self.down(),
+                        self.down_left(),
+                        self.left(),
+                        self.up_left(),
+                    ]
+                    .into_iter()
+                    .flatten()
+                }
+
+                /// The taxicab distance to `other`, moving only up, right, down or left.
+                #[must_use]
+                pub const fn manhattan(self, other: Self) -> $t {
+                    self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+                }
+
+                /// The distance to `other` when diagonal steps are allowed too.
+                #[must_use]
+                pub fn chebyshev(self, other: Self) -> $t {
+                    self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+                }
+
+                /// Each coordinate's sign, i.e. `0` or `1`.
+                #[must_use]
+                pub fn signum(self) -> Self {
+                    Self { x: <$t>::from(self.x > 0), y: <$t>::from(self.y > 0) }
+                }
+
+                #[must_use]
+                pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                    Some(Self { x: self.x.checked_add(rhs.x)?, y: self.y.checked_add(rhs.y)? })
+                }
+
+                #[must_use]
+                pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    Some(Self { x: self.x.checked_sub(rhs.x)?, y: self.y.checked_sub(rhs.y)? })
+                }
+
+                #[must_use]
+                pub fn checked_mul(self, rhs: $t) -> Option<Self> {
+                    Some(Self { x: self.x.checked_mul(rhs)?, y: self.y.checked_mul(rhs)? })
+                }
+            }
+        )*
+    };
+}
+
+impl_pos_signed!(i8, i16, i32, i64, i128, isize);
+impl_pos_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// A position or direction in 3D space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pos3<T> {
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T> Pos3<T>
+where
+    T: Copy,
+{
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    #[must_use]
+    pub const fn x(self) -> T {
+        self.x
+    }
+
+    #[must_use]
+    pub const fn y(self) -> T {
+        self.y
+    }
+
+    #[must_use]
+    pub const fn z(self) -> T {
+        self.z
+    }
+}
+
+impl<T> Add<Self> for Pos3<T>
+where
+    T: Add<T, Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T> AddAssign<Self> for Pos3<T>
+where
+    T: AddAssign<T>,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T> Sub<Self> for Pos3<T>
+where
+    T: Sub<T, Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T> SubAssign<Self> for Pos3<T>
+where
+    T: SubAssign<T>,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T> Mul<T> for Pos3<T>
+where
+    T: Copy + Mul<T, Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl<T> MulAssign<T> for Pos3<T>
+where
+    T: Copy + MulAssign<T>,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+impl<T> Neg for Pos3<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+macro_rules! impl_pos3_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Pos3<$t> {
+                pub const X: Self = Self::new(1, 0, 0);
+                pub const Y: Self = Self::new(0, 1, 0);
+                pub const Z: Self = Self::new(0, 0, 1);
+
+                /// The six positions sharing a face with `self`.
+                pub fn adj(self) -> impl Iterator<Item = Self> {
+                    [-Self::X, Self::X, -Self::Y, Self::Y, -Self::Z, Self::Z]
+                        .into_iter()
+                        .map(move |dir| self + dir)
+                }
+
+                /// The 26 positions sharing a face, edge or corner with `self`.
+                pub fn adj26(self) -> impl Iterator<Item = Self> {
+                    (-1..=1)
+                        .flat_map(|z| (-1..=1).flat_map(move |y| (-1..=1).map(move |x| (x, y, z))))
+                        .filter(|&dir| dir != (0, 0, 0))
+                        .map(move |(x, y, z)| self + Self::new(x, y, z))
+                }
+
+                #[must_use]
+                pub const fn dot(self, other: Self) -> $t {
+                    self.x * other.x + self.y * other.y + self.z * other.z
+                }
+
+                /// The vector perpendicular to both `self` and `other`, following the
+                /// right-hand rule, e.g. `X.cross(Y) == Z`.
+                #[must_use]
+                pub const fn cross(self, other: Self) -> Self {
+                    Self {
+                        x: self.y * other.z - self.z * other.y,
+                        y: self.z * other.x - self.x * other.z,
+                        z: self.x * other.y - self.y * other.x,
+                    }
+                }
+
+                #[must_use]
+                pub const fn manhattan(self, other: Self) -> $t {
+                    (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+                }
+            }
+
+            impl Mul<Pos3<$t>> for Rotation {
+                type Output = Pos3<$t>;
+
+                fn mul(self, pos: Pos3<$t>) -> Self::Output {
+                    let [x, y, z] = self.0.map(|[a, b, c]| {
+                        <$t>::from(a) * pos.x + <$t>::from(b) * pos.y + <$t>::from(c) * pos.z
+                    });
+
+                    Pos3 { x, y, z }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_pos3_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Pos3<$t> {
+                /// The positions sharing a face with `self`, skipping any that would overflow.
+                pub fn adj(self) -> impl Iterator<Item = Self> {
+                    [
+                        self.x.checked_sub(1).map(|x| Self { x, ..self }),
+                        self.x.checked_add(1).map(|x| Self { x, ..self }),
+                        self.y.checked_sub(1).map(|y| Self { y, ..self }),
+                        self.y.checked_add(1).map(|y| Self { y, ..self }),
+                        self.z.checked_sub(1).map(|z| Self { z, ..self }),
+                        self.z.checked_add(1).map(|z| Self { z, ..self }),
+                    ]
+                    .into_iter()
+                    .flatten()
+                }
+
+                /// The positions sharing a face, edge or corner with `self`, skipping any that
+                /// would overflow.
+                pub fn adj26(self) -> impl Iterator<Item = Self> {
+                    let range = |c: $t| c.saturating_sub(1)..=c.saturating_add(1);
+
+                    range(self.z)
+                        .flat_map(move |z| {
+                            range(self.y).flat_map(move |y| range(self.x).map(move |x| Self { x, y, z }))
+                        })
+                        .filter(move |&adj| adj != self)
+                }
+
+                #[must_use]
+                pub const fn manhattan(self, other: Self) -> $t {
+                    self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+                }
+            }
+        )*
+    };
+}
+
+impl_pos3_signed!(i8, i16, i32, i64, i128, isize);
+impl_pos3_unsigned!(u8, u16, u32, u64, u128, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// A rotation by a multiple of 90° about the axes, applied to a [`Pos3`] by multiplying.
+///
+/// Rotations compose like matrices: `(a * b) * pos == a * (b * pos)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rotation([[i8; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Self = Self([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// Rotates by `quarter_turns` × 90° about `axis`, counterclockwise when looking down the axis
+    /// towards the origin (so `X` turns `Y` into `Z`); negative turns go clockwise.
+    #[must_use]
+    pub fn about(axis: Axis, quarter_turns: i32) -> Self {
+        let quarter = match axis {
+            Axis::X => Self([[1, 0, 0], [0, 0, -1], [0, 1, 0]]),
+            Axis::Y => Self([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]),
+            Axis::Z => Self([[0, -1, 0], [1, 0, 0], [0, 0, 1]]),
+        };
+
+        (0..quarter_turns.rem_euclid(4)).fold(Self::IDENTITY, |rotation, _| rotation * quarter)
+    }
+
+    /// The rotation undoing this one.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        let m = self.0;
+
+        Self(std::array::from_fn(|i| std::array::from_fn(|j| m[j][i])))
+    }
+}
+
+impl Mul<Self> for Rotation {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.0, rhs.0);
+
+        Self(std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum())
+        }))
+    }
+}
+
+/// A rectangular grid of tiles, addressed by `Pos<usize>` with `(0, 0)` at the top left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    tiles: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` by `height` grid with every tile set to `tile`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, tile: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            tiles: vec![tile; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses a map with one character per tile, such as the puzzle inputs drawn with `#` and
+    /// `.`. Each character is mapped to a tile by `tile`, which is given the character's position
+    /// and the character itself as a slice of `map`, so that errors can point at it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the map is empty, its rows differ in length, or `tile` fails.
+    pub fn parse<'a>(
+        map: &'a str,
+        mut tile: impl FnMut(Pos<usize>, &'a str) -> Result<T>,
+    ) -> Result<Self> {
+        let width = map
+            .lines()
+            .next()
+            .filter(|row| !row.is_empty())
+            .ok_or_else(|| ParseError::new(map, "empty grid"))?
+            .chars()
+            .count();
+
+        let mut tiles = Vec::with_capacity(width * map.lines().count());
+        let mut height = 0;
+
+        for (y, row) in map.lines().enumerate() {
+            if row.chars().count() != width {
+                return Err(ParseError::new(row, format!("expected {width} tiles")).into());
+            }
+
+            for (x, c) in parse::chars(row).enumerate() {
+                tiles.push(tile(Pos::new(x, y), c)?);
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            tiles,
+            width,
+            height,
+        })
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub const fn contains(&self, pos: Pos<usize>) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Pos<usize>) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.tiles[(pos.y * self.width) + pos.x])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: Pos<usize>) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.tiles[(pos.y * self.width) + pos.x])
+    }
+
+    /// A grid of the same size, with every tile mapped by `f` from its position and the tile.
+    #[must_use]
+    pub fn map<U>(&self, mut f: impl FnMut(Pos<usize>, &T) -> U) -> Grid<U> {
+        Grid {
+            tiles: self.iter().map(|(pos, tile)| f(pos, tile)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos<usize>> + use<T> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    /// Every tile in the grid with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos<usize>, &T)> {
+        self.positions().zip(&self.tiles)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.tiles.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.tiles[x..].iter().step_by(self.width))
+    }
+
+    /// The positions up, right, down and left of `pos` that lie within the grid.
+    pub fn neighbors(&self, pos: Pos<usize>) -> impl Iterator<Item = Pos<usize>> {
+        pos.adj().filter(|&adj| self.contains(adj))
+    }
+
+    /// The positions around `pos`, including diagonally, that lie within the grid.
+    pub fn neighbors8(&self, pos: Pos<usize>) -> impl Iterator<Item = Pos<usize>> {
+        pos.adj8().filter(|&adj| self.contains(adj))
+    }
+}
+
+impl<T> Index<Pos<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos<usize>) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos<usize>) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+impl<T> std::fmt::Display for Grid<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for tile in row {
+                write!(f, "{tile}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pos_adj8() {
+        let pos = Pos::new(0_i32, 0);
+
+        assert_eq!(
+            pos.adj8().map(|adj| (adj.x(), adj.y())).collect::<Vec<_>>(),
+            [
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1)
+            ]
+        );
+        assert_eq!(Pos::new(0_usize, 1).adj8().count(), 5);
+        assert_eq!(Pos::new(0_usize, 0).up_right(), None);
+        assert_eq!(Pos::new(1_usize, 0).down_left(), Some(Pos::new(0, 1)));
+    }
+
+    #[test]
+    fn pos_distances() {
+        let (a, b) = (Pos::new(2_i64, 18), Pos::new(-2, 15));
+
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(b.manhattan(a), 7);
+        assert_eq!(Pos::new(3_u8, 1).manhattan(Pos::new(1, 4)), 5);
+        assert_eq!(Pos::new(3_u8, 1).chebyshev(Pos::new(1, 4)), 3);
+    }
+
+    #[test]
+    fn pos_signum_and_mul() {
+        assert_eq!(Pos::new(-5_i32, 0).signum(), Pos::new(-1, 0));
+        assert_eq!(Pos::new(4_u32, 0).signum(), Pos::new(1, 0));
+        assert_eq!(Pos::new(1_i32, -2) * 3, Pos::new(3, -6));
+
+        let mut pos = Pos::new(1_u8, 2);
+        pos *= 4;
+
+        assert_eq!(pos, Pos::new(4, 8));
+        assert_eq!(pos.checked_mul(64), None);
+        assert_eq!(pos.checked_add(Pos::new(1, 1)), Some(Pos::new(5, 9)));
+        assert_eq!(pos.checked_sub(Pos::new(5, 0)), None);
+    }
+
+    #[test]
+    fn pos3_adj() {
+        let pos = Pos3::new(1_i16, 1, 1);
+
+        assert_eq!(pos.adj().count(), 6);
+        assert!(pos.adj().all(|adj| adj.manhattan(pos) == 1));
+        assert_eq!(pos.adj26().count(), 26);
+        assert!(!pos.adj26().any(|adj| adj == pos));
+        assert_eq!(Pos3::new(0_u8, 0, 0).adj().count(), 3);
+        assert_eq!(Pos3::new(0_u8, 0, 0).adj26().count(), 7);
+    }
+
+    #[test]
+    fn pos3_products() {
+        let (x, y, z) = (Pos3::<i32>::X, Pos3::<i32>::Y, Pos3::<i32>::Z);
+
+        assert_eq!(x.cross(y), z);
+        assert_eq!(y.cross(z), x);
+        assert_eq!(y.cross(x), -z);
+        assert_eq!(Pos3::new(1_i32, 2, 3).dot(Pos3::new(4, -5, 6)), 12);
+        assert_eq!((x + y) * 3 - z, Pos3::new(3, 3, -1));
+    }
+
+    #[test]
+    fn rotation() {
+        let (x, y, z) = (Pos3::<i32>::X, Pos3::<i32>::Y, Pos3::<i32>::Z);
+        let about_x = Rotation::about(Axis::X, 1);
+
+        assert_eq!(about_x * y, z);
+        assert_eq!(Rotation::about(Axis::Y, 1) * z, x);
+        assert_eq!(Rotation::about(Axis::Z, 1) * x, y);
+        assert_eq!(Rotation::about(Axis::X, -1), Rotation::about(Axis::X, 3));
+        assert_eq!(about_x * about_x.inverse(), Rotation::IDENTITY);
+        assert_eq!(Rotation::about(Axis::Z, 4), Rotation::IDENTITY);
+
+        let rotation = about_x * Rotation::about(Axis::Z, 1);
+        let pos = Pos3::new(1, 2, 3);
+
+        assert_eq!(
+            rotation * pos,
+            about_x * (Rotation::about(Axis::Z, 1) * pos)
+        );
+    }
+
+    fn grid() -> Grid<u8> {
+        Grid::parse("123\n456\n", |_, tile| Ok(parse::from_str(tile)?)).unwrap()
+    }
+
+    #[test]
+    fn grid_get() {
+        let mut grid = self::grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Pos::new(2, 1)), Some(&6));
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.get(Pos::new(0, 2)), None);
+
+        *grid.get_mut(Pos::new(0, 1)).unwrap() = 0;
+        grid[Pos::new(1, 0)] += 5;
+
+        assert_eq!(grid.to_string(), "173\n056");
+    }
+
+    #[test]
+    fn grid_rows_and_columns() {
+        let grid = self::grid();
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+        assert_eq!(
+            grid.iter()
+                .map(|(pos, &tile)| (pos.x(), pos.y(), tile))
+                .last(),
+            Some((2, 1, 6))
+        );
+        assert_eq!(
+            grid.map(|pos, &tile| tile * 10 + u8::try_from(pos.y()).unwrap())
+                .to_string(),
+            "102030\n415161"
+        );
+    }
+
+    #[test]
+    fn grid_neighbors() {
+        let grid = self::grid();
+        let neighbors = |neighbors: &mut dyn Iterator<Item = Pos<usize>>| {
+            let mut neighbors = neighbors.map(|pos| grid[pos]).collect::<Vec<_>>();
+            neighbors.sort_unstable();
+            neighbors
+        };
+
+        assert_eq!(neighbors(&mut grid.neighbors(Pos::new(0, 0))), [2, 4]);
+        assert_eq!(neighbors(&mut grid.neighbors(Pos::new(1, 1))), [2, 4, 6]);
+        assert_eq!(neighbors(&mut grid.neighbors8(Pos::new(0, 0))), [2, 4, 5]);
+        assert_eq!(
+            neighbors(&mut grid.neighbors8(Pos::new(1, 1))),
+            [1, 2, 3, 4, 6]
+        );
+    }
+
+    #[test]
+    fn grid_parse_errors() {
+        let parse = |map| Grid::parse(map, |_, tile| Ok(parse::char::<char>(tile)?));
+        let message = |map| parse(map).unwrap_err().downcast::<ParseError>().unwrap();
+
+        assert_eq!(message("").message(), "empty grid");
+        assert_eq!(message("\n#").message(), "empty grid");
+        assert_eq!(message("#.#\n#.\n").message(), "expected 3 tiles");
+        assert_eq!(message("#.#\n#.\n").snippet(), "#.");
+        assert!(parse("#.#\n#.#\n").is_ok());
+    }
+}
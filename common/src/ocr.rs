@@ -0,0 +1,310 @@
+//! Reading the capital letters that some puzzles draw with `#` and `.`, such as the CRT of 2022's
+//! day 10, so that their answers can be checked like any other.
+//!
+//! Two fonts are known: letters 4 pixels wide and 6 tall, used by most of these puzzles, and the
+//! letters 6 wide and 10 tall of 2018's day 10. Letters are told apart by the blank columns
+//! between them, so a few of them may be narrower or wider than the rest, like `I` and `Y`.
+
+use std::ops::Range;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+
+/// A letter that is not in the font, pointing at the columns of the screen it was drawn in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    columns: Range<usize>,
+    glyph: String,
+}
+
+impl std::fmt::Display for UnknownGlyph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Range { start, end } = self.columns;
+
+        write!(
+            f,
+            "unknown glyph in columns {start}..{end}:\n{}",
+            self.glyph
+        )
+    }
+}
+
+impl std::error::Error for UnknownGlyph {}
+
+impl UnknownGlyph {
+    /// The columns of the screen the glyph spans.
+    #[must_use]
+    pub fn columns(&self) -> Range<usize> {
+        self.columns.clone()
+    }
+
+    /// The glyph as drawn, with `#` and `.`.
+    #[must_use]
+    pub fn glyph(&self) -> &str {
+        &self.glyph
+    }
+}
+
+/// Reads the letters drawn on `screen`, where `#` is a lit pixel and anything else is dark. The
+/// font is picked by the height of the screen.
+///
+/// # Errors
+///
+/// Returns an error if the screen is not as tall as either font or shows nothing, or an
+/// [`UnknownGlyph`] for the first letter that is not in the font.
+pub fn read(screen: &str) -> Result<String> {
+    let rows = screen.lines().collect::<Vec<_>>();
+    let font = self::font(rows.len())
+        .ok_or_else(|| anyhow!("no font is {} tall", self::pixels(rows.len())))?;
+    let glyphs = self::glyphs(&rows);
+
+    if glyphs.is_empty() {
+        bail!("nothing is drawn on the screen");
+    }
+
+    glyphs
+        .into_iter()
+        .map(|(columns, glyph)| {
+            font.iter()
+                .find(|(_, letter)| *letter == glyph)
+                .map(|&(c, _)| c)
+                .ok_or_else(|| {
+                    UnknownGlyph {
+                        columns,
+                        glyph: glyph.join("\n"),
+                    }
+                    .into()
+                })
+        })
+        .collect()
+}
+
+/// Draws `text` in the font that is `height` pixels tall, a blank column between letters, as the
+/// puzzles do, so that [`read`] reads it back.
+///
+/// # Errors
+///
+/// Returns an error if no font is that tall or a letter is not in it.
+pub fn draw(text: &str, height: usize) -> Result<String> {
+    let font =
+        self::font(height).ok_or_else(|| anyhow!("no font is {} tall", self::pixels(height)))?;
+    let glyphs = text
+        .chars()
+        .map(|c| {
+            font.iter()
+                .find(|(letter, _)| *letter == c)
+                .map(|(_, glyph)| glyph)
+                .ok_or_else(|| anyhow!("{c:?} is not in the font {} tall", self::pixels(height)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((0..height)
+        .map(|y| {
+            glyphs
+                .iter()
+                .map(|glyph| glyph[y].as_str())
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Splits the screen at its blank columns into glyphs, each with the columns it spans and its
+/// rows redrawn with `#` and `.`.
+fn glyphs(rows: &[&str]) -> Vec<(Range<usize>, Vec<String>)> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |x: usize, row: &str| row.as_bytes().get(x) == Some(&b'#');
+    let blank = |x: usize| !rows.iter().any(|row| lit(x, row));
+
+    let mut glyphs = Vec::new();
+    let mut x = 0;
+
+    while x < width {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !blank(x) {
+            x += 1;
+        }
+
+        let glyph = rows
+            .iter()
+            .map(|row| {
+                (start..x)
+                    .map(|x| if lit(x, row) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        glyphs.push((start..x, glyph));
+    }
+
+    glyphs
+}
+
+/// A number of pixels, e.g. "1 pixel" or "6 pixels".
+fn pixels(n: usize) -> String {
+    if n == 1 {
+        "1 pixel".to_string()
+    } else {
+        format!("{n} pixels")
+    }
+}
+
+/// The letters of the font that is `height` pixels tall.
+///
+/// Each font is drawn as it would be on a screen, a few letters at a time, and split into its
+/// letters the same way, so a letter must not have blank columns of its own.
+fn font(height: usize) -> Option<Vec<(char, Vec<String>)>> {
+    const SMALL: &[(&str, &str)] = &[
+        (
+            "ABCEFGHIJ",
+            "\
+.##..###...##..####.####..##..#..#.###...##
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#
+#..#.###..#....###..###..#....####..#.....#
+####.#..#.#....#....#....#.##.#..#..#.....#
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#
+#..#.###...##..####.#.....###.#..#.###..##.",
+        ),
+        (
+            "KLOPRSUYZ",
+            "\
+#..#.#.....##..###..###...###.#..#.#...#.####
+#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+#.#..#....#..#.###..###...##..#..#...#....#..
+#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.####..##..#....#..#.###...##....#...####",
+        ),
+    ];
+    const LARGE: &[(&str, &str)] = &[
+        (
+            "ABCEFGHJ",
+            "\
+..##...#####...####..######.######..####..#....#....###
+.#..#..#....#.#....#.#......#......#....#.#....#.....#.
+#....#.#....#.#......#......#......#......#....#.....#.
+#....#.#....#.#......#......#......#......#....#.....#.
+#....#.#####..#......#####..#####..#......######.....#.
+######.#....#.#......#......#......#..###.#....#.....#.
+#....#.#....#.#......#......#......#....#.#....#.....#.
+#....#.#....#.#......#......#......#....#.#....#.#...#.
+#....#.#....#.#....#.#......#......#...##.#....#.#...#.
+#....#.#####...####..######.#.......###.#.#....#..###..",
+        ),
+        (
+            "KLNPRXZ",
+            "\
+#....#.#......#....#.#####..#####..#....#.######
+#...#..#......##...#.#....#.#....#.#....#......#
+#..#...#......##...#.#....#.#....#..#..#.......#
+#.#....#......#.#..#.#....#.#....#..#..#......#.
+##.....#......#.#..#.#####..#####....##......#..
+##.....#......#..#.#.#......#..#.....##.....#...
+#.#....#......#..#.#.#......#...#...#..#...#....
+#..#...#......#...##.#......#...#...#..#..#.....
+#...#..#......#...##.#......#....#.#....#.#.....
+#....#.######.#....#.#......#....#.#....#.######",
+        ),
+    ];
+
+    let font = match height {
+        6 => SMALL,
+        10 => LARGE,
+        _ => return None,
+    };
+
+    Some(
+        font.iter()
+            .flat_map(|(letters, picture)| {
+                let rows = picture.lines().collect::<Vec<_>>();
+
+                letters
+                    .chars()
+                    .zip(self::glyphs(&rows).into_iter().map(|(_, glyph)| glyph))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read() {
+        for (height, letters) in [(6, "ABCEFGHIJKLOPRSUYZ"), (10, "ABCEFGHJKLNPRXZ")] {
+            assert_eq!(
+                super::read(&super::draw(letters, height).unwrap()).unwrap(),
+                letters
+            );
+        }
+
+        // As drawn by 2022's day 10, where every letter takes 5 columns
+        let screen = super::draw("EHZFZHCZ", 6).unwrap();
+
+        assert_eq!(screen.lines().next().unwrap().len(), 39);
+        assert_eq!(super::read(&screen).unwrap(), "EHZFZHCZ");
+    }
+
+    #[test]
+    fn font() {
+        for (height, letters) in [(6, 18), (10, 15)] {
+            let font = super::font(height).unwrap();
+
+            // Split into as many glyphs as there are letters, all of them different
+            assert_eq!(font.len(), letters);
+            assert!(font.iter().all(|(_, glyph)| glyph.len() == height));
+            assert!(
+                font.iter()
+                    .enumerate()
+                    .all(|(i, (_, glyph))| font[..i].iter().all(|(_, other)| other != glyph))
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_glyph() {
+        let screen = super::draw("HI", 6).unwrap().replace("###\n", "#.#\n");
+        let err = super::read(&screen)
+            .unwrap_err()
+            .downcast::<UnknownGlyph>()
+            .unwrap();
+
+        assert_eq!(err.columns(), 5..8);
+        assert_eq!(err.glyph(), "#.#\n.#.\n.#.\n.#.\n.#.\n###");
+        assert!(
+            err.to_string()
+                .starts_with("unknown glyph in columns 5..8:\n")
+        );
+    }
+
+    #[test]
+    fn blank_or_wrong_height() {
+        assert!(super::read("....\n....\n....\n....\n....\n....").is_err());
+        assert_eq!(
+            super::read("#").unwrap_err().to_string(),
+            "no font is 1 pixel tall"
+        );
+        assert!(super::read("").is_err());
+    }
+
+    #[test]
+    fn draw() {
+        assert_eq!(
+            super::draw("HI", 6).unwrap(),
+            "#..#.###\n#..#..#.\n####..#.\n#..#..#.\n#..#..#.\n#..#.###"
+        );
+        assert_eq!(
+            super::draw("HI", 7).unwrap_err().to_string(),
+            "no font is 7 pixels tall"
+        );
+        assert!(super::draw("hi", 6).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A malformed puzzle input, pointing at the offending part of the input.
+///
+/// Parsers create it from the slice of their input they failed to parse. [`Day::parse`] then
+/// finds that slice in the whole puzzle input, filling in the day, line and column, and
+/// `Display` quotes the line with a caret under the offending characters:
+///
+/// ```text
+/// day 10, line 3, column 1: unknown instruction
+///   |
+/// 3 | jump 3
+///   | ^^^^
+/// ```
+///
+/// [`Day::parse`]: crate::Day::parse
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    snippet: String,
+    /// Address range of the snippet, used to find it within the puzzle input.
+    span: Range<usize>,
+    day: Option<u8>,
+    location: Option<Location>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Location {
+    line: usize,
+    column: usize,
+    text: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}")?;
+
+            if let Some(Location { line, column, .. }) = self.location {
+                write!(f, ", line {line}, column {column}")?;
+            }
+
+            write!(f, ": ")?;
+        }
+
+        write!(f, "{}", self.message)?;
+
+        let Some(Location { line, column, text }) = &self.location else {
+            return if self.snippet.is_empty() {
+                Ok(())
+            } else {
+                write!(f, " '{}'", self.snippet)
+            };
+        };
+
+        let gutter = line.to_string().len();
+        let carets = self
+            .snippet
+            .lines()
+            .next()
+            .map_or(0, |snippet| snippet.chars().count())
+            .max(1);
+
+        writeln!(f)?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{line} | {text}")?;
+        write!(
+            f,
+            "{:gutter$} | {:offset$}{}",
+            "",
+            "",
+            "^".repeat(carets),
+            offset = column - 1
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    /// Creates an error for `snippet`, which should be a slice of the puzzle input so that it can
+    /// be located; an empty slice points between two characters, e.g. at the end of a line.
+    #[must_use]
+    pub fn new(snippet: &str, message: impl Display) -> Self {
+        let start = snippet.as_ptr().addr();
+
+        Self {
+            message: message.to_string(),
+            snippet: snippet.to_string(),
+            span: start..start + snippet.len(),
+            day: None,
+            location: None,
+        }
+    }
+
+    /// Attributes the error to `day` and locates its snippet within the puzzle `input`, if the
+    /// snippet was sliced from it.
+    #[must_use]
+    pub fn locate(mut self, day: u8, input: &str) -> Self {
+        self.day = Some(day);
+
+        let start = input.as_ptr().addr();
+
+        if self.span.start < start || self.span.end > start + input.len() {
+            return self;
+        }
+
+        let offset = self.span.start - start;
+        let (before, after) = input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = after.find('\n').map_or(input.len(), |i| offset + i);
+
+        self.location = Some(Location {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        });
+
+        self
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[must_use]
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    #[must_use]
+    pub const fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    /// The 1-based line of the snippet, once located.
+    #[must_use]
+    pub fn line(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.line)
+    }
+
+    /// The 1-based column of the snippet's first character, once located.
+    #[must_use]
+    pub fn column(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.column)
+    }
+}
+
+/// Parses `snippet` with its `FromStr` implementation, reporting failures at `snippet`.
+///
+/// # Errors
+///
+/// Returns an error if `snippet` is not a valid `T`.
+pub fn from_str<T>(snippet: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    snippet.parse().map_err(|err| ParseError::new(snippet, err))
+}
+
+/// Parses `snippet`, which must be a single character, with its `TryFrom<char>` implementation.
+///
+/// # Errors
+///
+/// Returns an error if `snippet` is not a single character or not a valid `T`.
+pub fn char<T>(snippet: &str) -> Result<T, ParseError>
+where
+    T: TryFrom<char>,
+    T::Error: Display,
+{
+    let mut chars = snippet.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => T::try_from(c).map_err(|err| ParseError::new(snippet, err)),
+        _ => Err(ParseError::new(snippet, "expected a single character")),
+    }
+}
+
+/// Strips `prefix` from `s`, reporting its absence at the start of `s`.
+///
+/// # Errors
+///
+/// Returns an error if `s` does not start with `prefix`.
+pub fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix).ok_or_else(|| {
+        let snippet = s.get(..prefix.len()).unwrap_or(s);
+
+        ParseError::new(snippet, format!("expected '{}'", prefix.trim_end()))
+    })
+}
+
+/// Splits `s` into one slice per character, so that each can be reported with [`ParseError`].
+#[must_use]
+pub fn chars(s: &str) -> impl DoubleEndedIterator<Item = &str> {
+    s.char_indices().map(|(i, c)| &s[i..i + c.len_utf8()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate() {
+        let input = "noop\naddx 3\njump 3\n";
+        let line = input.lines().nth(2).unwrap();
+        let err = ParseError::new(&line[..4], "unknown instruction").locate(10, input);
+
+        assert_eq!(err.day(), Some(10));
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.column(), Some(1));
+        assert_eq!(err.snippet(), "jump");
+        assert_eq!(
+            err.to_string(),
+            "day 10, line 3, column 1: unknown instruction\n  |\n3 | jump 3\n  | ^^^^"
+        );
+    }
+
+    #[test]
+    fn locate_end_of_line() {
+        let input = "Sensor at x=2, y=18\n";
+        let err = ParseError::new(&input[19..19], "missing beacon").locate(15, input);
+
+        assert_eq!(err.line(), Some(1));
+        assert_eq!(err.column(), Some(20));
+        assert!(err.to_string().ends_with(&format!(
+            "\n1 | Sensor at x=2, y=18\n  | {}^",
+            " ".repeat(19)
+        )));
+    }
+
+    #[test]
+    fn unlocated() {
+        let err = ParseError::new("1x", "invalid digit found in string");
+
+        assert_eq!(err.line(), None);
+        assert_eq!(err.to_string(), "invalid digit found in string '1x'");
+        assert_eq!(
+            err.locate(1, "100\n200\n").to_string(),
+            "day 1: invalid digit found in string '1x'"
+        );
+    }
+
+    #[test]
+    fn chars() {
+        assert_eq!(super::chars("<€>").collect::<Vec<_>>(), ["<", "€", ">"]);
+        assert_eq!(super::char::<char>("€"), Ok('€'));
+        assert_eq!(
+            super::char::<char>("<>").unwrap_err().message(),
+            "expected a single character"
+        );
+        assert_eq!(
+            from_str::<u8>("3x").unwrap_err().message(),
+            "invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! What the puzzle simulations are rendered as, to be drawn by the runner.
+//!
+//! A [`Render`] type draws its state as a [`Frame`] of [`Cell`]s, which only say what kind of
+//! thing is where, e.g. a wall or a falling grain of sand, and leave the colours and the image
+//! format to whatever draws them.
+//!
+//! Frames are placed by the position of their top left cell, so drawings that grow as the
+//! simulation runs stay in place from one frame to the next.
+
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+
+use crate::Grid;
+use crate::Pos;
+
+/// A state that can be drawn as a frame of cells.
+pub trait Render {
+    fn render(&self) -> Frame;
+}
+
+/// What a cell of a frame shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// Outside of the map, e.g. around the edges of the day 22 board.
+    Void,
+    /// Open ground or air.
+    Empty,
+    Wall,
+    /// What has come to rest or been left behind, e.g. sand, rocks or a trail.
+    Filled,
+    /// What is moving, e.g. a falling grain of sand, elves or blizzards.
+    Active,
+    /// A single point of interest, e.g. the source of the sand or the expedition.
+    Marker,
+}
+
+/// The cells drawn by [`Render::render`], with the position of the top left cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    origin: Pos<i64>,
+    cells: Grid<Cell>,
+}
+
+impl Cell {
+    pub const ALL: [Self; 6] = [
+        Self::Void,
+        Self::Empty,
+        Self::Wall,
+        Self::Filled,
+        Self::Active,
+        Self::Marker,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Void => "void",
+            Self::Empty => "empty",
+            Self::Wall => "wall",
+            Self::Filled => "filled",
+            Self::Active => "active",
+            Self::Marker => "marker",
+        }
+    }
+}
+
+impl FromStr for Cell {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|cell| cell.name() == name)
+            .ok_or_else(|| anyhow!("unknown cell '{name}'"))
+    }
+}
+
+impl Frame {
+    /// Creates a frame of `cells`, with the top left cell at `origin`.
+    #[must_use]
+    pub const fn new(origin: Pos<i64>, cells: Grid<Cell>) -> Self {
+        Self { origin, cells }
+    }
+
+    #[must_use]
+    pub const fn origin(&self) -> Pos<i64> {
+        self.origin
+    }
+
+    #[must_use]
+    pub const fn cells(&self) -> &Grid<Cell> {
+        &self.cells
+    }
+
+    /// The position of `pos` within the frame's cells, if it lies within the frame.
+    fn offset(&self, pos: Pos<i64>) -> Option<Pos<usize>> {
+        let offset = pos - self.origin;
+        let x = usize::try_from(offset.x()).ok()?;
+        let y = usize::try_from(offset.y()).ok()?;
+
+        Some(Pos::new(x, y)).filter(|&pos| self.cells.contains(pos))
+    }
+
+    /// The cell at `pos`, or [`Cell::Void`] outside the frame.
+    #[must_use]
+    pub fn get(&self, pos: Pos<i64>) -> Cell {
+        self.offset(pos).map_or(Cell::Void, |pos| self.cells[pos])
+    }
+
+    /// Sets the cell at `pos`, ignoring positions outside the frame.
+    pub fn set(&mut self, pos: Pos<i64>, cell: Cell) {
+        if let Some(pos) = self.offset(pos) {
+            self.cells[pos] = cell;
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! Generic graph searches over states produced by a successor closure.
+//!
+//! States are anything hashable, e.g. a `Pos`, or a `(Pos, time)` pair when the map changes over
+//! time. The searches that stop at a goal return the whole [`Path`] to it, not just its cost.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The states visited from the start to the goal, both included, and the cost of getting there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S, C> {
+    states: Vec<S>,
+    cost: C,
+}
+
+impl<S, C> Path<S, C>
+where
+    C: Copy,
+{
+    #[must_use]
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    #[must_use]
+    pub fn into_states(self) -> Vec<S> {
+        self.states
+    }
+
+    /// The number of steps for a breadth-first search, or the sum of the step costs otherwise.
+    #[must_use]
+    pub const fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// The state that satisfied the goal.
+    #[must_use]
+    pub fn goal(&self) -> &S {
+        self.states
+            .last()
+            .unwrap_or_else(|| unreachable!("a path has at least its start"))
+    }
+}
+
+/// A visited state, linked to the state it was first (or most cheaply) reached from.
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+}
+
+/// Follows the parents of `nodes[goal]` back to the start.
+fn path<S, C>(nodes: &[Node<S, C>], goal: usize) -> Path<S, C>
+where
+    S: Clone,
+    C: Copy,
+{
+    let mut states = Vec::new();
+    let mut i = Some(goal);
+
+    while let Some(node) = i.map(|i| &nodes[i]) {
+        states.push(node.state.clone());
+        i = node.parent;
+    }
+
+    states.reverse();
+
+    Path {
+        states,
+        cost: nodes[goal].cost,
+    }
+}
+
+/// Finds a path with the fewest steps from `start` to a state satisfying `goal`.
+#[must_use]
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut idxs = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![Node {
+        state: start,
+        parent: None,
+        cost: 0,
+    }];
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        if goal(&nodes[i].state) {
+            return Some(self::path(&nodes, i));
+        }
+
+        for next in successors(&nodes[i].state) {
+            if let Entry::Vacant(entry) = idxs.entry(next) {
+                let j = nodes.len();
+
+                nodes.push(Node {
+                    state: entry.key().clone(),
+                    parent: Some(i),
+                    cost: nodes[i].cost + 1,
+                });
+                entry.insert(j);
+                queue.push_back(j);
+            }
+        }
+    }
+
+    None
+}
+
+/// The fewest steps from `start` to every state reachable from it.
+#[must_use]
+pub fn distances<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut dists = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, dist)) = queue.pop_front() {
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = dists.entry(next) {
+                queue.push_back((entry.key().clone(), dist + 1));
+                entry.insert(dist + 1);
+            }
+        }
+    }
+
+    dists
+}
+
+/// Finds a cheapest path from `start` to a state satisfying `goal`, where `successors` yields
+/// each next state along with the cost of stepping to it.
+#[must_use]
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    self::astar(start, successors, goal, |_| C::default())
+}
+
+/// Like [`dijkstra`], but first explores the states that `heuristic` estimates to be closest to
+/// the goal.
+///
+/// The path is only guaranteed to be cheapest if the heuristic never overestimates the remaining
+/// cost, e.g. the Manhattan distance to the goal on a grid.
+#[must_use]
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut idxs = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![Node {
+        state: start,
+        parent: None,
+        cost: C::default(),
+    }];
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > nodes[i].cost {
+            // Reached more cheaply since this entry was pushed
+            continue;
+        }
+
+        if goal(&nodes[i].state) {
+            return Some(self::path(&nodes, i));
+        }
+
+        for (next, step) in successors(&nodes[i].state) {
+            let next_cost = cost + step;
+
+            let j = match idxs.entry(next) {
+                Entry::Vacant(entry) => {
+                    let j = nodes.len();
+
+                    nodes.push(Node {
+                        state: entry.key().clone(),
+                        parent: Some(i),
+                        cost: next_cost,
+                    });
+                    entry.insert(j);
+                    j
+                }
+                Entry::Occupied(entry) => {
+                    let j = *entry.get();
+
+                    if next_cost >= nodes[j].cost {
+                        continue;
+                    }
+
+                    nodes[j].parent = Some(i);
+                    nodes[j].cost = next_cost;
+                    j
+                }
+            };
+
+            heap.push(Reverse((
+                next_cost + heuristic(&nodes[j].state),
+                next_cost,
+                j,
+            )));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+    use crate::Pos;
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.
+";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, |_, tile| Ok(tile == "#")).unwrap()
+    }
+
+    fn open(maze: &Grid<bool>, pos: Pos<usize>) -> impl Iterator<Item = Pos<usize>> {
+        maze.neighbors(pos).filter(|&adj| !maze[adj])
+    }
+
+    #[test]
+    fn bfs() {
+        let maze = self::maze();
+        let goal = Pos::new(6, 0);
+        let path = super::bfs(
+            Pos::new(0, 0),
+            |&pos| self::open(&maze, pos),
+            |&pos| pos == goal,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost(), 12);
+        assert_eq!(path.states().len(), 13);
+        assert_eq!(path.states()[0], Pos::new(0, 0));
+        assert_eq!(*path.goal(), goal);
+        assert!(
+            path.states()
+                .windows(2)
+                .all(|step| step[0].manhattan(step[1]) == 1 && !maze[step[1]])
+        );
+        assert_eq!(
+            super::bfs(
+                Pos::new(0, 0),
+                |&pos| self::open(&maze, pos),
+                |&pos| pos == Pos::new(2, 0)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let maze = self::maze();
+        let dists = super::distances(Pos::new(0, 0), |&pos| self::open(&maze, pos));
+
+        assert_eq!(dists[&Pos::new(0, 0)], 0);
+        assert_eq!(dists[&Pos::new(2, 3)], 5);
+        assert_eq!(dists[&Pos::new(6, 0)], 12);
+        assert_eq!(dists.len(), maze.iter().filter(|&(_, &wall)| !wall).count());
+    }
+
+    #[test]
+    fn dijkstra() {
+        // The direct road is expensive, so the cheapest path takes the detour through 'b' and 'c'
+        let roads = HashMap::from([
+            ('a', vec![('b', 1), ('d', 10)]),
+            ('b', vec![('c', 2)]),
+            ('c', vec![('d', 3), ('a', 1)]),
+            ('d', vec![]),
+        ]);
+        let path = super::dijkstra('a', |city| roads[city].clone(), |&city| city == 'd').unwrap();
+
+        assert_eq!(path.cost(), 6);
+        assert_eq!(path.into_states(), ['a', 'b', 'c', 'd']);
+        assert_eq!(
+            super::dijkstra('d', |city| roads[city].clone(), |&city| city == 'a'),
+            None
+        );
+    }
+
+    #[test]
+    fn astar() {
+        let maze = self::maze();
+        let goal = Pos::new(6, 3);
+        let successors = |&pos: &Pos<usize>| self::open(&maze, pos).map(|adj| (adj, 1));
+        let path = super::astar(
+            Pos::new(0, 0),
+            successors,
+            |&pos| pos == goal,
+            |pos| pos.manhattan(goal),
+        )
+        .unwrap();
+        let shortest = super::dijkstra(Pos::new(0, 0), successors, |&pos| pos == goal).unwrap();
+
+        assert_eq!(path.cost(), shortest.cost());
+        assert_eq!(path.cost(), 11);
+        assert_eq!(*path.goal(), goal);
+    }
+}
@@ -0,0 +1,16 @@
+//! Puzzle simulations that can be stepped through, to be played back or rendered by the runner.
+
+use crate::render::Render;
+
+/// A puzzle simulation that can be advanced one step at a time, e.g. one grain of sand falling
+/// one tile, and drawn with `Display` or [`Render`] after every step.
+pub trait Simulation: std::fmt::Display + Render {
+    /// Advances the simulation by one step, returning `false` once it has finished.
+    fn step(&mut self) -> bool;
+}
+
+/// Steps `simulation` until it finishes or `limit` steps have been taken, returning the number
+/// of steps taken.
+pub fn run(simulation: &mut dyn Simulation, limit: usize) -> usize {
+    (0..limit).take_while(|_| simulation.step()).count()
+}